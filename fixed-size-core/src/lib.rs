@@ -0,0 +1,3415 @@
+//! Implementation of the `fixed-size` attribute macro, usable from other proc macros
+//! 
+//! [`fixed`] and [`fixed_ty`] expand the macros of the same name in `fixed-size`, which only
+//! forwards to them. [`transform_field`] rewrites a single field the way `#[fixed]` would, for
+//! macros which need the same `String` to `ArrayString<N>` transformation.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use std::collections::{HashMap, HashSet};
+use syn::{ext::IdentExt, parse::{Parse, ParseStream, Result}, Token, punctuated::Punctuated,
+                  fold::Fold, Expr, Ident, LitInt, LitStr, Lit,
+                  Item, ItemStruct, Type, Field, parse_quote, PathSegment, PathArguments,
+                  GenericArgument, TypePath, Path, Member, Index, ExprLit, FieldsNamed, BinOp,
+                  FieldsUnnamed, Visibility, GenericParam, WherePredicate};
+
+/// field name or tuple index -> options given for it
+type MapType = HashMap<Member, FieldArgs>;
+
+/// name and size in chars of a `unit=chars` field, whose type is sized in bytes
+type CharsOf = (String, Size);
+
+/// Options given for one field, either `field=N` or `field=(N, ...)`
+#[derive(Clone)]
+struct FieldArgs {
+    num: Size,
+    /// per-field replacement type
+    typ: Option<Path>,
+    /// minimum length in bytes checked by the generated `validate`
+    min: Option<LitInt>,
+    /// pad serialized strings on the left
+    right: Option<bool>,
+    /// keep pad bytes when deserializing rather than trimming them
+    keep_pad: Option<bool>,
+    /// byte used to pad serialized strings
+    pad: Option<u8>,
+    /// generate a `set_<field>_num` writing numbers zero padded to the size
+    zero_pad: Option<bool>,
+    /// size of each string in a `Vec<String>`, given as `field=(N, M)`
+    inner: Option<Size>,
+}
+
+impl FieldArgs {
+    fn new(num: Size) -> Self {
+        FieldArgs { num, typ: None, min: None, right: None, keep_pad: None, pad: None, zero_pad: None, inner: None }
+    }
+}
+
+/// How a fixed string is padded, for one field or the struct
+#[derive(Clone, Copy, Default)]
+struct Padding {
+    pad: u8,
+    right: bool,
+    keep: bool,
+}
+#[derive(Default)]
+struct Args {
+    size_map: MapType,
+    /// size for string fields not in `size_map`
+    wildcard: Option<Size>,
+    /// replacement type for string fields, from `str_typ` or `typ`
+    typ: Option<Path>,
+    /// replacement type for vector fields, from `vec_typ` or `typ`
+    vec_typ: Option<Path>,
+    /// option keys given outside `fields(...)` whose values could be mistaken for sizes
+    options: Vec<Ident>,
+    /// full path of the `schema` file sizes were read from, so the build tracks it
+    schema_file: Option<String>,
+    /// generic arguments for the global `typ`, with `{}` replaced by the size
+    typ_shape: Option<LitStr>,
+    /// generate `set_<field>` methods for fixed string fields
+    gen_try_from: bool,
+    /// generate a `FIXED_LEN` const
+    gen_len: bool,
+    /// generate a `FIXED_FIELDS` table of the fixed fields and their sizes
+    gen_schema: bool,
+    /// generate a `Default` impl with empty or zeroed fixed fields
+    gen_default: bool,
+    /// rewrite string fields of size 1 to `char`
+    as_char: bool,
+    /// generate a borrowing accessor for each fixed string and byte field
+    gen_accessors: bool,
+    /// generate a `const fn <field>_capacity()` for each fixed field
+    gen_capacity: bool,
+    /// what generated setters do with values longer than the capacity
+    on_overflow: Overflow,
+    /// generate a string wrapper which serializes padded to its capacity
+    serde_pad: bool,
+    /// generate a byte vector wrapper which serializes its length followed by the bytes
+    serde_len: bool,
+    /// keep `String` fields, generating `serialize_with`/`deserialize_with` functions which pad
+    /// them to their size on the wire
+    serde_with: bool,
+    /// module the `serde_with` functions are generated in, while folding
+    with_module: Option<Ident>,
+    /// name of the generated length prefixed wrapper, while folding with `serde_len`
+    prefixed: Option<Ident>,
+    /// generate a `Display` impl writing the trimmed string fields
+    gen_display: bool,
+    /// generate a `Debug` impl showing each fixed field's capacity next to its value
+    gen_debug: bool,
+    /// emit a `const` assertion that each fixed field's size is non-zero
+    gen_static_asserts: bool,
+    /// generate `to_bytes` and `from_bytes` for the fixed length layout, implies `FIXED_LEN`
+    gen_bytes: bool,
+    /// generate `from_bytes_ref` borrowing a `#[repr(C)]` struct of byte arrays, implies `FIXED_LEN`
+    gen_bytes_ref: bool,
+    /// write numbers big-endian in `to_bytes` rather than little-endian
+    big_endian: bool,
+    /// check byte arrays hold UTF-8 up to the first pad byte in `from_bytes` and `validate`
+    validate_utf8: bool,
+    /// leave field types alone, only generating the helpers and a `validate` checking lengths
+    no_rewrite: bool,
+    /// error for fixed fields without a `#[serde(rename = "...")]`
+    require_rename: bool,
+    /// sizes given by serde name, moved into `size_map` once the fields are known
+    serde_names: Vec<(LitStr, FieldArgs)>,
+    /// generate `<field>_eq` methods comparing fixed string fields to a `&str`
+    gen_str_eq: bool,
+    /// error for listed fields which are already fixed, rather than leaving them alone
+    strict: bool,
+    /// count string sizes in chars rather than bytes
+    chars: bool,
+    /// count sizes in KiB rather than bytes
+    kib: bool,
+    /// pad serialized strings on the left, unless given for the field
+    right: bool,
+    /// keep pad bytes when deserializing, unless given for the field
+    keep_pad: bool,
+    /// byte used to pad serialized strings
+    pad: u8,
+    /// generate a `set_<field>_num` for each string field, unless given for the field
+    zero_pad: bool,
+    /// name of the generated padded string wrapper, while folding with `serde_pad`
+    padded: Option<Ident>,
+    /// wrap each fixed string field in its own newtype
+    gen_newtypes: bool,
+    /// generate `<field>_try_push_str` methods appending to fixed string fields
+    gen_mutators: bool,
+    /// generate `<field>_writer` methods giving a `fmt::Write` appending to fixed string fields
+    gen_write: bool,
+    /// store sized fields of other types, taken to be C-like enums, as integers of that size
+    enum_as_int: bool,
+    /// rewrite sized fields of other types as strings, e.g. aliases of `String`
+    force: bool,
+    /// the fields stored as integers by `enum_as_int`, with their enum and integer types
+    enums: Vec<(Member, Type, Type)>,
+    /// name of the struct the newtypes are named after, while folding with `gen_newtypes`
+    newtype_prefix: Option<Ident>,
+    /// the newtypes generated while folding and the type each wraps, with the field's name and
+    /// size in chars with `unit=chars`
+    newtypes: Vec<(Ident, Type, Option<CharsOf>)>,
+    /// the struct's const generic parameters, which sizes may name
+    const_params: HashSet<Ident>,
+    /// emit the fixed struct as a sibling named with this suffix, leaving the original alone
+    suffix: Option<Ident>,
+    /// fields whose type is replaced by an already fixed struct, `field: From -> To`
+    nested: HashMap<Member, (Type, Type)>,
+    /// path to `arrayvec`, or a re-export of it, for the default types and generated code
+    krate: Option<Path>,
+    /// hints for a `#[repr]` added to the fixed struct
+    repr: Option<Vec<Ident>>,
+    /// fields deliberately left alone, including by the wildcard
+    skip: HashSet<Member>,
+    /// the variable length field after the fixed header, also in `skip`
+    tail: Option<Member>,
+    /// fields left out of the code generated for a feature, e.g. `"debug"` for `gen_debug`,
+    /// with the `exclude_<feature>` key they were given by
+    excluded: HashMap<&'static str, (Ident, HashSet<Member>)>,
+    /// helper items handed on by an outer `#[fixed]` on the same struct, with the names to
+    /// re-export, for this one to emit alongside its own
+    forwarded: (Vec<Ident>, TokenStream),
+    /// keys of `size_map` which matched a field while folding
+    seen: HashSet<Member>,
+    /// fields rewritten while folding, in declaration order
+    fixed: Vec<Fixed>,
+    /// `#[cfg]` attributes of the fields which have them, repeated on the code generated for each
+    cfgs: HashMap<Member, Vec<syn::Attribute>>,
+    /// errors found while folding
+    errors: Option<syn::Error>,
+}
+
+/// Overflow handling for generated setters
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum Overflow {
+    #[default]
+    Error,
+    Truncate,
+}
+
+/// What a fixed field was rewritten from
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// `String`, `Box<str>` or `Cow<str>`
+    Str,
+    /// `Option<String>`
+    OptionStr,
+    /// `String`, `Box<str>` or `Cow<str>` of size 1 with `as_char`
+    Char,
+    /// `Vec<u8>` or `Bytes`
+    Bytes,
+    /// `Option<Vec<u8>>` or `Option<Bytes>`
+    OptionBytes,
+    /// any other `Vec<T>`
+    Vec,
+    /// the `String` key of a `HashMap` or `BTreeMap`, given as `<field>_key`
+    MapKey,
+    /// `Vec<String>` given a size for its strings as `field=(N, M)`
+    StrVec,
+}
+
+impl Fixed {
+    /// Whether the field was rewritten to `typ`, or in an `Option` of it
+    fn ty_is(&self, typ: Option<&Ident>) -> bool {
+        let mut ty = &self.ty;
+        if matches!(self.kind, Kind::OptionStr | Kind::OptionBytes) {
+            if let Type::Path(p) = ty {
+                if let Some(inner) = p.path.segments.last().and_then(generic_arg) {
+                    ty = inner;
+                }
+            }
+        }
+        matches!((ty, typ), (Type::Path(p), Some(typ)) if p.path.segments.first().is_some_and(|s| s.ident == *typ))
+    }
+}
+
+/// How a field is laid out in a fixed length representation of the struct
+enum Layout<'a> {
+    /// a string padded to its capacity
+    Str(&'a Size),
+    /// a byte array
+    Bytes(&'a Size),
+    /// a byte vector with a `u32` length prefix
+    Prefixed(&'a Size),
+    /// a primitive number
+    Num(&'a Type),
+    /// a struct generated by `#[fixed(gen_len)]`
+    Nested(&'a Type),
+    /// a field fixed before this `#[fixed]` saw it, e.g. by another stacked on the struct, with
+    /// its length taken from the type
+    Stacked(proc_macro2::TokenStream),
+}
+
+impl Layout<'_> {
+    /// Expression for the length in bytes
+    fn len(&self) -> proc_macro2::TokenStream {
+        match self {
+            Layout::Str(num) | Layout::Bytes(num) => quote!(#num),
+            Layout::Prefixed(num) => quote!((::core::mem::size_of::<u32>() + #num)),
+            Layout::Num(ty) => quote!(::core::mem::size_of::<#ty>()),
+            Layout::Nested(ty) => quote!(<#ty>::FIXED_LEN),
+            Layout::Stacked(len) => len.clone(),
+        }
+    }
+}
+
+/// Length in bytes of the already fixed `ty`, a byte array or a string whose only generic
+/// argument is its capacity, such as `ArrayString<4>`
+fn stacked_len(ty: &Type) -> Option<proc_macro2::TokenStream> {
+    let Type::Path(p) = ty else {
+        return matches!(ty, Type::Array(_)).then(|| quote!(::core::mem::size_of::<#ty>()));
+    };
+    let PathArguments::AngleBracketed(args) = &p.path.segments.last()?.arguments else {
+        return None;
+    };
+    match args.args.iter().collect::<Vec<_>>()[..] {
+        [GenericArgument::Const(len)] => Some(quote!({ #len })),
+        _ => None,
+    }
+}
+
+/// Whether `ty` is a primitive integer or float
+fn is_numeric(ty: &Type) -> bool {
+    const NUMERIC: &[&str] = &["u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64"];
+    matches!(ty, Type::Path(p) if NUMERIC.iter().any(|n| p.path.is_ident(n)))
+}
+
+/// Whether `ty` already looks fixed length: an array, or a type like `ArrayString<4>` with a
+/// const generic argument, possibly in an `Option`
+fn is_fixed(ty: &Type) -> bool {
+    match ty {
+        Type::Array(_) => true,
+        Type::Path(p) => p.path.segments.last().is_some_and(|seg| match &seg.arguments {
+            PathArguments::AngleBracketed(a) => a.args.iter().any(|arg| match arg {
+                GenericArgument::Const(_) => true,
+                GenericArgument::Type(inner) => seg.ident == "Option" && is_fixed(inner),
+                _ => false,
+            }),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+/// A field rewritten by the fold, used to generate helpers
+struct Fixed {
+    member: Member,
+    kind: Kind,
+    ty: Type,
+    num: Size,
+    min: Option<LitInt>,
+    /// the size in chars with `unit=chars`, `num` is then the bytes needed
+    chars: Option<Size>,
+    /// the strings of a `Kind::StrVec`
+    inner: Option<Box<Fixed>>,
+}
+
+impl Fixed {
+    /// Length of the string `v`, in chars with `unit=chars` and otherwise in bytes
+    fn len(&self, v: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        if self.chars.is_some() { quote!(#v.chars().count()) } else { quote!(#v.len()) }
+    }
+
+    /// `error_ty::Overflow` for this field, with `got` in scope as the length found
+    fn overflow(&self, error_ty: &Ident) -> proc_macro2::TokenStream {
+        let (field, cap) = (field_name(&self.member), self.chars.as_ref().unwrap_or(&self.num));
+        quote!(#error_ty::Overflow { field: #field, cap: #cap, got })
+    }
+
+    /// Convert the `&str` `v` to the field's type, returning `error_ty::Overflow` if it's too long
+    fn try_convert(&self, v: proc_macro2::TokenStream, error_ty: &Ident) -> proc_macro2::TokenStream {
+        let (len, overflow) = (self.len(quote!(v)), self.overflow(error_ty));
+        let cap = self.chars.as_ref().unwrap_or(&self.num);
+        quote! {{
+            let v: &str = #v;
+            let got = #len;
+            if got > #cap {
+                return ::core::result::Result::Err(#overflow);
+            }
+            ::core::convert::TryFrom::try_from(v).map_err(|_| #overflow)?
+        }}
+    }
+
+    /// Collect the bytes or items `v` into `ty`, a byte array or vector, returning
+    /// `error_ty::Overflow` if there are too many
+    fn collect(&self, v: proc_macro2::TokenStream, ty: &Type, error_ty: &Ident) -> proc_macro2::TokenStream {
+        let (num, overflow) = (&self.num, self.overflow(error_ty));
+        let fill = match ty {
+            Type::Array(_) => quote! {
+                let mut a = [0u8; #num];
+                a[..got].copy_from_slice(&items);
+            },
+            _ => quote! {
+                let mut a: #ty = ::core::default::Default::default();
+                a.extend(items);
+            },
+        };
+        quote! {{
+            let items = #v;
+            let got = items.len();
+            if got > #num {
+                return ::core::result::Result::Err(#overflow);
+            }
+            #fill
+            a
+        }}
+    }
+}
+
+/// The `gen_<feature>` options fields can be left out of with `exclude_<feature>(...)`
+const EXCLUDABLE: [&str; 7] = ["accessors", "capacity", "debug", "display", "mutators", "str_eq", "write"];
+
+const ERRMSG: &str = "Must specify an Ident=Int, Ident=(Int, Structname), typ=Structname or an option";
+
+const SHAPE: &str = "typ_shape must be a string of generic arguments with `{}` for the size, e.g. \"<[u8; {}]>\"";
+
+const UNSUPPORTED: &str =
+    "don't know how to make this type fixed length, expected String, Box<str>, Cow<str>, Option<String>, Vec<T> or Bytes";
+
+/// Extract the key of an assignment, which must be a bare identifier
+fn key_ident(p: &syn::ExprPath) -> Result<&Ident> {
+    p.path.get_ident().ok_or_else(|| {
+        syn::Error::new_spanned(&p.path, "field name must be a single identifier, got a path")
+    })
+}
+
+/// Extract the field an assignment refers to, a bare identifier or a tuple index
+fn key_member(e: &Expr) -> Result<Member> {
+    match e {
+        Expr::Path(p) => Ok(Member::Named(key_ident(p)?.clone())),
+        Expr::Lit(ExprLit { lit: Lit::Int(i), .. }) => Ok(Member::Unnamed(Index { index: i.base10_parse()?, span: i.span() })),
+        Expr::Field(f) => {
+            let msg = format!("`{}` is a field of a nested struct, which #[fixed] can't reach into, put `#[fixed({}=N)]` on that struct instead",
+                quote!(#e).to_string().replace(' ', ""), member_name(&f.member));
+            Err(syn::Error::new_spanned(e, msg))
+        }
+        _ => Err(syn::Error::new_spanned(e, ERRMSG)),
+    }
+}
+
+/// Human readable name of a field for messages
+fn member_name(m: &Member) -> String {
+    match m {
+        Member::Named(i) => i.to_string(),
+        Member::Unnamed(i) => i.index.to_string(),
+    }
+}
+
+/// Name of a field for generated code to report, without any `r#`
+fn field_name(m: &Member) -> String {
+    match m {
+        Member::Named(i) => i.unraw().to_string(),
+        Member::Unnamed(i) => i.index.to_string(),
+    }
+}
+
+/// Validate a size literal, a zero capacity fixed field can never hold anything. The result is
+/// unsuffixed so `4usize` or `0x4` emit the same const generic argument as `4`.
+fn check_size(num: &LitInt) -> Result<LitInt> {
+    let size = num.base10_parse::<usize>()
+        .map_err(|_| syn::Error::new_spanned(num, format!("size {} is too large to fit in a usize", num.base10_digits())))?;
+    if size == 0 {
+        return Err(syn::Error::new_spanned(num, "size must be greater than zero"));
+    }
+    Ok(LitInt::new(&size.to_string(), num.span()))
+}
+
+/// The first of `params` named anywhere in `tokens`
+fn mentioned(tokens: TokenStream, params: &HashSet<Ident>) -> Option<Ident> {
+    tokens.into_iter().find_map(|t| match t {
+        proc_macro2::TokenTree::Ident(i) if params.contains(&i) => Some(i),
+        proc_macro2::TokenTree::Group(g) => mentioned(g.stream(), params),
+        _ => None,
+    })
+}
+
+/// The capacity of a fixed field
+#[derive(Clone)]
+enum Size {
+    /// an integer literal, normalized by `check_size`
+    Lit(LitInt),
+    /// a path to a `const`
+    Const(Path),
+    /// one of the struct's own const generic parameters
+    Param(Ident),
+    /// arithmetic on `const`s, evaluated by the compiler
+    Expr(Box<Expr>),
+    /// the worst case bytes for a number of chars, with `unit=chars`
+    Chars(Box<Size>),
+    /// the bytes in a number of KiB, with `unit=kib`
+    Kib(Box<Size>),
+}
+
+impl Size {
+    /// Use a const generic parameter of the struct directly, if this names one of `params`
+    fn param(&mut self, params: &HashSet<Ident>) {
+        if let Size::Const(path) = self {
+            if let Some(ident) = path.get_ident().filter(|i| params.contains(*i)) {
+                *self = Size::Param(ident.clone());
+            }
+        }
+    }
+
+    /// Bytes needed for this many chars of UTF-8
+    fn chars(&self) -> Size {
+        match self {
+            Size::Lit(num) => match num.base10_parse::<usize>().ok().and_then(|n| n.checked_mul(4)) {
+                Some(bytes) => Size::Lit(LitInt::new(&bytes.to_string(), num.span())),
+                // left to overflow at compile time, where it's reported
+                None => Size::Chars(Box::new(self.clone())),
+            },
+            _ => Size::Chars(Box::new(self.clone())),
+        }
+    }
+
+    /// Bytes in this many KiB
+    fn kib(&self) -> Size {
+        match self {
+            Size::Lit(num) => match num.base10_parse::<usize>().ok().and_then(|n| n.checked_mul(1024)) {
+                Some(bytes) => Size::Lit(LitInt::new(&bytes.to_string(), num.span())),
+                // left to overflow at compile time, where it's reported
+                None => Size::Kib(Box::new(self.clone())),
+            },
+            _ => Size::Kib(Box::new(self.clone())),
+        }
+    }
+}
+
+impl ToTokens for Size {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            Size::Lit(num) => num.to_tokens(tokens),
+            // braced since a bare path isn't always accepted as a const generic argument
+            Size::Const(path) => quote!({ #path }).to_tokens(tokens),
+            Size::Param(ident) => ident.to_tokens(tokens),
+            Size::Expr(e) => quote!({ #e }).to_tokens(tokens),
+            Size::Chars(num) => quote!({ 4 * #num }).to_tokens(tokens),
+            Size::Kib(num) => quote!({ 1024 * #num }).to_tokens(tokens),
+        }
+    }
+}
+
+/// Parse the size given for a field, an integer literal, a path to a `const` or arithmetic on
+/// them
+fn size(e: &Expr) -> Result<Size> {
+    match e {
+        Expr::Lit(ExprLit { lit: Lit::Int(num), .. }) => Ok(Size::Lit(check_size(num)?)),
+        Expr::Path(p) => Ok(Size::Const(p.path.clone())),
+        Expr::Call(c) if matches!(&*c.func, Expr::Path(f) if f.path.is_ident("env")) => Ok(Size::Lit(env_size(c)?)),
+        Expr::Binary(_) | Expr::Paren(_) => match size_expr(e)? {
+            // only literals, so it's checked like one
+            Some(n) => {
+                let num = LitInt::new(&n.to_string(), syn::spanned::Spanned::span(e));
+                Ok(Size::Lit(check_size(&num).map_err(|err| syn::Error::new_spanned(e, err))?))
+            }
+            None => Ok(Size::Expr(Box::new(e.clone()))),
+        },
+        _ => Err(syn::Error::new_spanned(e, ERRMSG)),
+    }
+}
+
+/// Read the size given as `env("NAME")` from that environment variable while expanding
+fn env_size(call: &syn::ExprCall) -> Result<LitInt> {
+    let name = match call.args.first() {
+        Some(Expr::Lit(ExprLit { lit: Lit::Str(name), .. })) if call.args.len() == 1 => name,
+        _ => return Err(syn::Error::new_spanned(call, "env takes the name of a variable as a string, e.g. env(\"REC_LEN\")")),
+    };
+    let value = std::env::var(name.value()).map_err(|e| {
+        let msg = match e {
+            std::env::VarError::NotPresent => format!("environment variable `{}` isn't set", name.value()),
+            std::env::VarError::NotUnicode(_) => format!("environment variable `{}` isn't valid unicode", name.value()),
+        };
+        syn::Error::new_spanned(name, msg)
+    })?;
+    let num = value.trim().parse::<usize>().map_err(|_| {
+        let msg = format!("environment variable `{}` must hold a size, found `{}`", name.value(), value);
+        syn::Error::new_spanned(name, msg)
+    })?;
+    let num = LitInt::new(&num.to_string(), name.span());
+    check_size(&num).map_err(|e| syn::Error::new_spanned(name, e))
+}
+
+/// The value of a size expression made only of integer literals, or `None` if it names a `const`
+fn size_expr(e: &Expr) -> Result<Option<u128>> {
+    let value = match e {
+        Expr::Lit(ExprLit { lit: Lit::Int(num), .. }) => num.base10_parse::<u128>().ok(),
+        Expr::Path(_) => return Ok(None),
+        Expr::Paren(p) => return size_expr(&p.expr),
+        Expr::Call(c) if matches!(&*c.func, Expr::Path(f) if f.path.is_ident("env")) => env_size(c)?.base10_parse::<u128>().ok(),
+        Expr::Binary(b) => {
+            let op: fn(u128, u128) -> Option<u128> = match b.op {
+                BinOp::Add(_) => u128::checked_add,
+                BinOp::Sub(_) => u128::checked_sub,
+                BinOp::Mul(_) => u128::checked_mul,
+                BinOp::Div(_) => u128::checked_div,
+                BinOp::Rem(_) => u128::checked_rem,
+                _ => return Err(syn::Error::new_spanned(b.op, "size expressions may only use `+`, `-`, `*`, `/` and `%`")),
+            };
+            match (size_expr(&b.left)?, size_expr(&b.right)?) {
+                (Some(l), Some(r)) => op(l, r),
+                _ => return Ok(None),
+            }
+        }
+        _ => return Err(syn::Error::new_spanned(e, "size expressions may only use integers, `const`s and `env(...)`")),
+    };
+    value.map(Some).ok_or_else(|| syn::Error::new_spanned(e, "size expression overflows or divides by zero"))
+}
+
+/// Parse `key=N` or `key=(N, ...)` for a field and add it to `size_map`
+fn field(size_map: &mut MapType, key: &Expr, val: &Expr) -> Result<()> {
+    let key = key_member(key)?;
+    let field = field_args(&member_name(&key), val)?;
+    insert(size_map, key, field)
+}
+
+/// Parse the `N` or `(N, ...)` given for the field `name`
+fn field_args(name: &str, val: &Expr) -> Result<FieldArgs> {
+    Ok(match val {
+        Expr::Tuple(t) => {
+            let mut elems = t.elems.iter();
+            let Some(num) = elems.next() else { return Err(syn::Error::new_spanned(val, ERRMSG)) };
+            let mut field = FieldArgs::new(field_size(name, num)?);
+            for e in elems {
+                field_option(&mut field, e)?;
+            }
+            if let (Some(min), Size::Lit(num)) = (&field.min, field.inner.as_ref().unwrap_or(&field.num)) {
+                if min.base10_parse::<usize>()? > num.base10_parse::<usize>()? {
+                    return Err(syn::Error::new_spanned(min, "min must not be greater than the size"));
+                }
+            }
+            field
+        }
+        _ => FieldArgs::new(field_size(name, val)?),
+    })
+}
+
+/// Read `field=size` lines from the file named by `lit`, relative to `CARGO_MANIFEST_DIR`,
+/// returning them and the full path of the file
+fn schema_file(lit: &LitStr) -> Result<(MapType, String)> {
+    let path = lit.value();
+    let full = std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default()).join(&path);
+    let text = std::fs::read_to_string(&full)
+        .map_err(|e| syn::Error::new_spanned(lit, format!("couldn't read schema file `{}`: {}", path, e)))?;
+    let mut size_map = MapType::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = match line.parse().ok().and_then(|tokens| syn::parse2(respan(tokens, lit.span())).ok()) {
+            Some(Expr::Assign(a)) => field(&mut size_map, &a.left, &a.right),
+            _ => Err(syn::Error::new(lit.span(), "expected `field=size`")),
+        };
+        if let Err(e) = entry {
+            return Err(syn::Error::new_spanned(lit, format!("{}:{}: {}", path, n + 1, e)));
+        }
+    }
+    Ok((size_map, full.display().to_string()))
+}
+
+/// `tokens` with every span set to `span`, so errors for them point at where they came from
+fn respan(tokens: proc_macro2::TokenStream, span: Span) -> proc_macro2::TokenStream {
+    tokens.into_iter().map(|mut t| {
+        if let proc_macro2::TokenTree::Group(g) = &t {
+            let mut group = proc_macro2::Group::new(g.delimiter(), respan(g.stream(), span));
+            group.set_span(span);
+            t = group.into();
+        }
+        t.set_span(span);
+        t
+    }).collect()
+}
+
+/// Parse the size for field `key`, naming it if the literal isn't an integer
+fn field_size(name: &str, e: &Expr) -> Result<Size> {
+    let Expr::Lit(ExprLit { lit, .. }) = e else { return size(e) };
+    let found = match lit {
+        Lit::Int(_) => return size(e),
+        Lit::Float(_) => "float",
+        Lit::Str(_) => "string",
+        Lit::ByteStr(_) => "byte string",
+        Lit::CStr(_) => "C string",
+        Lit::Byte(_) => "byte",
+        Lit::Char(_) => "character",
+        Lit::Bool(_) => "bool",
+        _ => "literal",
+    };
+    let msg = format!("size for field `{}` must be an integer literal, found {}", name, found);
+    Err(syn::Error::new_spanned(lit, msg))
+}
+
+/// Add a `serde:"name"=N` size, rejecting names which were already given
+fn insert_serde(serde_names: &mut Vec<(LitStr, FieldArgs)>, name: LitStr, val: &Expr) -> Result<()> {
+    if serde_names.iter().any(|(n, _)| n.value() == name.value()) {
+        let msg = format!("field `{}` specified more than once", name.value());
+        return Err(syn::Error::new_spanned(name, msg));
+    }
+    let args = field_args(&name.value(), val)?;
+    serde_names.push((name, args));
+    Ok(())
+}
+
+/// Add a `field: From -> To` substitution, rejecting fields which were already given
+fn insert_nested(nested: &mut HashMap<Member, (Type, Type)>, member: Member, types: (Type, Type)) -> Result<()> {
+    if nested.contains_key(&member) {
+        let msg = format!("field `{}` specified more than once", member_name(&member));
+        return Err(syn::Error::new_spanned(member, msg));
+    }
+    nested.insert(member, types);
+    Ok(())
+}
+
+/// Parse the generic arguments of `typ_shape="<...>"`, checking they make a type with `{}` and
+/// `{T}` filled in
+fn shape(e: &Expr) -> Result<LitStr> {
+    let Expr::Lit(ExprLit { lit: Lit::Str(shape), .. }) = e else {
+        return Err(syn::Error::new_spanned(e, SHAPE));
+    };
+    let args = shape.value().replace("{T}", "u8").replace("{}", "1");
+    if !shape.value().contains("{}") || syn::parse_str::<TypePath>(&format!("T{}", args)).is_err() {
+        return Err(syn::Error::new_spanned(shape, SHAPE));
+    }
+    Ok(shape.clone())
+}
+
+/// Parse `pad` or `none`, returning whether pad bytes are kept when deserializing
+fn trim_policy(e: &Expr) -> Result<bool> {
+    match e {
+        Expr::Path(p) if p.path.is_ident("pad") => Ok(false),
+        Expr::Path(p) if p.path.is_ident("none") => Ok(true),
+        _ => Err(syn::Error::new_spanned(e, "trim must be `pad` or `none`")),
+    }
+}
+
+/// Parse `zero_pad` or `none`, returning whether numbers are zero padded
+fn num_fmt(e: &Expr) -> Result<bool> {
+    match e {
+        Expr::Path(p) if p.path.is_ident("zero_pad") => Ok(true),
+        Expr::Path(p) if p.path.is_ident("none") => Ok(false),
+        _ => Err(syn::Error::new_spanned(e, "num_fmt must be `zero_pad` or `none`")),
+    }
+}
+
+/// Parse the ASCII character or byte given to `pad`
+fn pad_byte(v: &ExprLit) -> Result<u8> {
+    match &v.lit {
+        Lit::Char(c) if c.value().is_ascii() => Ok(c.value() as u8),
+        Lit::Int(i) => match i.base10_parse::<u8>() {
+            Ok(b) if b.is_ascii() => Ok(b),
+            _ => Err(syn::Error::new_spanned(i, "pad must be an ASCII byte")),
+        },
+        _ => Err(syn::Error::new_spanned(v, "pad must be an ASCII character or byte")),
+    }
+}
+
+/// Parse `left` or `right`, returning whether it's right aligned
+fn align(e: &Expr) -> Result<bool> {
+    match e {
+        Expr::Path(p) if p.path.is_ident("left") => Ok(false),
+        Expr::Path(p) if p.path.is_ident("right") => Ok(true),
+        _ => Err(syn::Error::new_spanned(e, "align must be `left` or `right`")),
+    }
+}
+
+/// Parse one of the options after the size in `field=(N, ...)`
+fn field_option(field: &mut FieldArgs, e: &Expr) -> Result<()> {
+    match e {
+        Expr::Lit(ExprLit { lit: Lit::Int(inner), .. }) => {
+            if field.inner.is_some() {
+                return Err(syn::Error::new_spanned(e, "string size specified more than once"));
+            }
+            field.inner = Some(Size::Lit(check_size(inner)?));
+        }
+        Expr::Path(typ) => {
+            if field.typ.is_some() {
+                return Err(syn::Error::new_spanned(e, "type specified more than once"));
+            }
+            field.typ = Some(typ.path.clone());
+        }
+        Expr::Assign(a) => {
+            let Expr::Path(p) = &*a.left else {
+                return Err(syn::Error::new_spanned(&a.left, "expected a field option name"));
+            };
+            let key = key_ident(p)?;
+            match (key.to_string().as_str(), &*a.right) {
+                ("min", Expr::Lit(ExprLit { lit: Lit::Int(min), .. })) => set_once(&mut field.min, key, check_size(min)?)?,
+                ("min", v) => return Err(syn::Error::new_spanned(v, "min must be an integer")),
+                ("align", v) => set_once(&mut field.right, key, align(v)?)?,
+                ("trim", v) => set_once(&mut field.keep_pad, key, trim_policy(v)?)?,
+                ("num_fmt", v) => set_once(&mut field.zero_pad, key, num_fmt(v)?)?,
+                ("pad", Expr::Lit(v)) => set_once(&mut field.pad, key, pad_byte(v)?)?,
+                ("pad", v) => return Err(syn::Error::new_spanned(v, "pad must be an ASCII character or byte")),
+                _ => return Err(syn::Error::new_spanned(key, format!("unknown field option `{}`", key))),
+            }
+        }
+        _ => return Err(syn::Error::new_spanned(e, "expected a type or a field option like `min=N`")),
+    }
+    Ok(())
+}
+
+/// Add a field to `size_map`, rejecting fields which were already given
+fn insert(size_map: &mut MapType, key: Member, val: FieldArgs) -> Result<()> {
+    if size_map.contains_key(&key) {
+        return Err(syn::Error::new_spanned(&key, format!("field `{}` specified more than once", member_name(&key))));
+    }
+    size_map.insert(key, val);
+    Ok(())
+}
+
+/// Set an option which may only be given once
+fn set_once<T>(slot: &mut Option<T>, key: &Ident, val: T) -> Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(key, format!("`{}` specified more than once", key)));
+    }
+    *slot = Some(val);
+    Ok(())
+}
+
+/// Parse one `repr` hint, which must be one that makes sense for a fixed struct
+fn repr_hint(e: &Expr) -> Result<Ident> {
+    match e {
+        Expr::Path(p) => match p.path.get_ident() {
+            Some(i) if i == "C" || i == "packed" || i == "transparent" => Ok(i.clone()),
+            _ => Err(syn::Error::new_spanned(e, "repr must be `C`, `packed` or `transparent`")),
+        },
+        _ => Err(syn::Error::new_spanned(e, "repr must be `C`, `packed` or `transparent`")),
+    }
+}
+
+/// One comma separated argument to the attribute
+enum Arg {
+    /// `*=N`
+    Wildcard(Token![*], Expr),
+    /// `field: From -> To`
+    Nested(Member, Box<(Type, Type)>),
+    /// `crate=path`
+    Crate(Token![crate], Path),
+    /// `fields(...)`, holding only field options
+    Fields(Ident, Punctuated<Arg, Token![,]>),
+    /// `skip(...)`, fields deliberately left variable length
+    Skip(Punctuated<Member, Token![,]>),
+    /// `tail(field)`, the variable length field after the fixed header
+    Tail(Ident, Member),
+    /// `exclude_<feature>(...)`, fields left out of the code one `gen_<feature>` generates
+    Exclude(Ident, Punctuated<Member, Token![,]>),
+    /// `serde:"name"=N`, a field named by its serde rename
+    Serde(LitStr, Expr),
+    /// `__helpers(names) { items }`, helpers handed on by an outer `#[fixed]` on the struct
+    Helpers(Ident, Punctuated<Ident, Token![,]>, TokenStream),
+    Expr(Expr),
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![*]) {
+            let star = input.parse()?;
+            input.parse::<Token![=]>()?;
+            return Ok(Arg::Wildcard(star, input.parse()?));
+        }
+        if input.peek(Token![crate]) && input.peek2(Token![=]) {
+            let krate = input.parse()?;
+            input.parse::<Token![=]>()?;
+            return Ok(Arg::Crate(krate, input.call(Path::parse_mod_style)?));
+        }
+        if input.peek(Ident) && input.peek2(syn::token::Paren) {
+            let fork = input.fork();
+            let ident = fork.parse::<Ident>()?;
+            if ident == "__helpers" {
+                let ident = input.parse()?;
+                let (names, items);
+                syn::parenthesized!(names in input);
+                syn::braced!(items in input);
+                return Ok(Arg::Helpers(ident, names.parse_terminated(Ident::parse, Token![,])?, items.parse()?));
+            }
+            if ident == "fields" || ident == "skip" || ident == "tail" || ident.to_string().starts_with("exclude_") {
+                let ident: Ident = input.parse()?;
+                let content;
+                syn::parenthesized!(content in input);
+                if ident == "skip" {
+                    return Ok(Arg::Skip(content.parse_terminated(Member::parse, Token![,])?));
+                }
+                if ident != "fields" && ident != "tail" {
+                    return Ok(Arg::Exclude(ident, content.parse_terminated(Member::parse, Token![,])?));
+                }
+                if ident == "tail" {
+                    let member = content.parse()?;
+                    content.parse::<Option<Token![,]>>()?;
+                    if !content.is_empty() {
+                        return Err(content.error("a struct has only one tail, expected `tail(field)`"));
+                    }
+                    return Ok(Arg::Tail(ident, member));
+                }
+                return Ok(Arg::Fields(ident, content.parse_terminated(Arg::parse, Token![,])?));
+            }
+        }
+        if input.peek(Ident) && input.peek2(Token![:]) && !input.peek2(Token![::]) {
+            let fork = input.fork();
+            if fork.parse::<Ident>()? == "serde" && fork.parse::<Token![:]>().is_ok() && fork.peek(LitStr) {
+                input.parse::<Ident>()?;
+                input.parse::<Token![:]>()?;
+                let name = input.parse()?;
+                input.parse::<Token![=]>()?;
+                return Ok(Arg::Serde(name, input.parse()?));
+            }
+        }
+        if (input.peek(Ident::peek_any) || input.peek(LitInt)) && input.peek2(Token![:]) && !input.peek2(Token![::]) {
+            let member = input.parse()?;
+            input.parse::<Token![:]>()?;
+            let from = input.parse()?;
+            input.parse::<Token![->]>()?;
+            return Ok(Arg::Nested(member, Box::new((from, input.parse()?))));
+        }
+        Ok(Arg::Expr(input.parse()?))
+    }
+}
+
+/// Re-emits an argument so errors can be spanned at just that argument
+impl ToTokens for Arg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Arg::Wildcard(star, val) => quote!(#star = #val),
+            Arg::Nested(member, types) => {
+                let (from, to) = &**types;
+                quote!(#member: #from -> #to)
+            }
+            Arg::Crate(krate, path) => quote!(#krate = #path),
+            Arg::Fields(ident, args) => quote!(#ident(#args)),
+            Arg::Skip(members) => quote!(skip(#members)),
+            Arg::Tail(ident, member) => quote!(#ident(#member)),
+            Arg::Exclude(ident, members) => quote!(#ident(#members)),
+            Arg::Helpers(ident, names, items) => quote!(#ident(#names) { #items }),
+            Arg::Serde(name, val) => quote!(serde: #name = #val),
+            Arg::Expr(expr) => quote!(#expr),
+        });
+    }
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let vars = Punctuated::<Arg, Token![,]>::parse_terminated(input)?;
+        let mut size_map = MapType::new();
+        let mut typ = None;
+        let mut str_typ = None;
+        let mut vec_typ = None;
+        let mut gen_try_from = false;
+        let mut gen_len = false;
+        let mut gen_accessors = false;
+        let mut gen_capacity = false;
+        let mut as_char = false;
+        let mut gen_default = false;
+        let mut gen_schema = false;
+        let mut strict = false;
+        let mut gen_display = false;
+        let mut gen_debug = false;
+        let mut gen_static_asserts = false;
+        let mut gen_bytes = false;
+        let mut gen_bytes_ref = false;
+        let mut require_rename = false;
+        let mut validate_utf8 = false;
+        let mut no_rewrite = false;
+        let mut gen_newtypes = false;
+        let mut gen_mutators = false;
+        let mut gen_write = false;
+        let mut enum_as_int = false;
+        let mut force = false;
+        let mut gen_str_eq = false;
+        let mut typ_shape = None;
+        let mut keep_pad = None;
+        let mut options = Vec::new();
+        let mut schema = None;
+        let mut skip = HashSet::new();
+        let mut tail = None;
+        let mut forwarded = (Vec::new(), TokenStream::new());
+        let mut excluded = HashMap::<&'static str, (Ident, HashSet<Member>)>::new();
+        let mut serde_names = Vec::new();
+        let mut serde_pad = false;
+        let mut serde_with = false;
+        let mut serde_len = false;
+        let mut on_overflow = None;
+        let mut suffix = None;
+        let mut pad = None;
+        let mut wildcard = None;
+        let mut nested = HashMap::new();
+        let mut repr = None;
+        let mut krate = None;
+        let mut right = None;
+        let mut zero_pad = None;
+        let mut unit = None;
+        let mut big_endian = None;
+        // each argument is checked on its own so that every mistake is reported at once
+        let mut errors: Option<syn::Error> = None;
+        let mut arg = |var: Arg| -> Result<()> {
+            let var = match var {
+                Arg::Wildcard(star, num) => {
+                    if wildcard.is_some() {
+                        return Err(syn::Error::new_spanned(star, "`*` specified more than once"));
+                    }
+                    wildcard = Some(size(&num)?);
+                    return Ok(());
+                }
+                Arg::Crate(token, path) => {
+                    if krate.is_some() {
+                        return Err(syn::Error::new_spanned(token, "`crate` specified more than once"));
+                    }
+                    krate = Some(path);
+                    return Ok(());
+                }
+                Arg::Nested(member, types) => {
+                    insert_nested(&mut nested, member, *types)?;
+                    return Ok(());
+                }
+                Arg::Skip(members) => {
+                    for member in members {
+                        if !skip.insert(member.clone()) {
+                            let msg = format!("field `{}` skipped more than once", member_name(&member));
+                            return Err(syn::Error::new_spanned(member, msg));
+                        }
+                    }
+                    return Ok(());
+                }
+                Arg::Tail(ident, member) => {
+                    // left alone like a skipped field, but kept out of `FIXED_LEN`
+                    skip.insert(member.clone());
+                    set_once(&mut tail, &ident, member)?;
+                    return Ok(());
+                }
+                Arg::Helpers(_, names, items) => {
+                    forwarded.0.extend(names);
+                    forwarded.1.extend(items);
+                    return Ok(());
+                }
+                Arg::Exclude(ident, members) => {
+                    let feature = ident.to_string();
+                    let Some(feature) = EXCLUDABLE.iter().find(|f| feature.strip_prefix("exclude_") == Some(**f)) else {
+                        let msg = format!("unknown option `{}`, fields can be excluded from `{}`", ident,
+                            EXCLUDABLE.map(|f| format!("gen_{}", f)).join("`, `"));
+                        return Err(syn::Error::new_spanned(ident, msg));
+                    };
+                    if excluded.contains_key(feature) {
+                        return Err(syn::Error::new_spanned(&ident, format!("`{}` specified more than once", ident)));
+                    }
+                    let (_, fields) = excluded.entry(feature).or_insert((ident, HashSet::new()));
+                    for member in members {
+                        if !fields.insert(member.clone()) {
+                            let msg = format!("field `{}` excluded more than once", member_name(&member));
+                            return Err(syn::Error::new_spanned(member, msg));
+                        }
+                    }
+                    return Ok(());
+                }
+                Arg::Serde(name, val) => {
+                    insert_serde(&mut serde_names, name, &val)?;
+                    return Ok(());
+                }
+                Arg::Fields(_, args) => {
+                    for arg in args {
+                        // Errors underline the whole entry, not the `fields` key
+                        let entry = arg.to_token_stream();
+                        let at_entry = |e: syn::Error| syn::Error::new_spanned(&entry, e);
+                        match arg {
+                            Arg::Nested(member, types) => insert_nested(&mut nested, member, *types).map_err(at_entry)?,
+                            Arg::Expr(Expr::Assign(a)) => field(&mut size_map, &a.left, &a.right).map_err(at_entry)?,
+                            Arg::Serde(name, val) => insert_serde(&mut serde_names, name, &val).map_err(at_entry)?,
+                            _ => return Err(syn::Error::new_spanned(&entry,
+                                "expected only `field=N` or `field: From -> To` inside `fields(...)`")),
+                        }
+                    }
+                    return Ok(());
+                }
+                Arg::Expr(Expr::Assign(var)) => var,
+                Arg::Expr(Expr::Path(p)) => {
+                    let flag = key_ident(&p)?;
+                    let set = match flag.to_string().as_str() {
+                        "gen_try_from" => &mut gen_try_from,
+                        "gen_len" => &mut gen_len,
+                        "gen_accessors" => &mut gen_accessors,
+                        "gen_capacity" => &mut gen_capacity,
+                        "as_char" => &mut as_char,
+                        "gen_default" => &mut gen_default,
+                        "gen_schema" => &mut gen_schema,
+                        "strict" => &mut strict,
+                        "gen_display" => &mut gen_display,
+                        "gen_debug" => &mut gen_debug,
+                        "gen_static_asserts" => &mut gen_static_asserts,
+                        "gen_bytes" => &mut gen_bytes,
+                        "gen_bytes_ref" => &mut gen_bytes_ref,
+                        "require_rename" => &mut require_rename,
+                        "validate_utf8" => &mut validate_utf8,
+                        "no_rewrite" => &mut no_rewrite,
+                        "gen_newtypes" => &mut gen_newtypes,
+                        "gen_mutators" => &mut gen_mutators,
+                        "gen_write" => &mut gen_write,
+                        "enum_as_int" => &mut enum_as_int,
+                        "force" => &mut force,
+                        "gen_str_eq" => &mut gen_str_eq,
+                        "serde_pad" => &mut serde_pad,
+                        "serde_len" => &mut serde_len,
+                        "serde_with" => &mut serde_with,
+                        _ => return Err(syn::Error::new_spanned(flag, format!("unknown option `{}`", flag))),
+                    };
+                    if *set {
+                        return Err(syn::Error::new_spanned(flag, format!("`{}` specified more than once", flag)));
+                    }
+                    *set = true;
+                    return Ok(());
+                }
+                Arg::Expr(other) => return Err(syn::Error::new_spanned(other, ERRMSG)),
+            };
+            match (&*var.left, &*var.right) {
+                (Expr::Path(p), v) if ["typ", "str_typ", "vec_typ"].iter().any(|k| p.path.is_ident(k)) && !matches!(v, Expr::Path(_)) => {
+                    let mut msg = format!("{0} must be a type path, e.g. {0}=MyString", quote!(#p));
+                    if matches!(v, Expr::Lit(ExprLit { lit: Lit::Int(_), .. })) {
+                        msg += &format!(", or size a field named `{}` inside `fields(...)`", quote!(#p));
+                    }
+                    return Err(syn::Error::new_spanned(v, msg));
+                }
+                (Expr::Path(p), Expr::Lit(ExprLit { lit: Lit::Str(path), .. })) if p.path.is_ident("schema") => {
+                    let (file, full) = schema_file(path)?;
+                    set_once(&mut schema, key_ident(p)?, (file, full))?;
+                }
+                (Expr::Path(p), v) if p.path.is_ident("typ_shape") => {
+                    set_once(&mut typ_shape, key_ident(p)?, shape(v)?)?;
+                }
+                (Expr::Path(p), Expr::Path(v)) => {
+                    let key = key_ident(p)?;
+                    match key.to_string().as_str() {
+                        "typ" => set_once(&mut typ, key, v.path.clone())?,
+                        "str_typ" => set_once(&mut str_typ, key, v.path.clone())?,
+                        "vec_typ" => set_once(&mut vec_typ, key, v.path.clone())?,
+                        "on_overflow" => {
+                            let val = match v.path.get_ident() {
+                                Some(v) if v == "error" => Overflow::Error,
+                                Some(v) if v == "truncate" => Overflow::Truncate,
+                                _ => return Err(syn::Error::new_spanned(v, "on_overflow must be `error` or `truncate`")),
+                            };
+                            set_once(&mut on_overflow, key, val)?;
+                        }
+                        "align" => set_once(&mut right, key, align(&var.right)?)?,
+                        "trim" => set_once(&mut keep_pad, key, trim_policy(&var.right)?)?,
+                        "num_fmt" => set_once(&mut zero_pad, key, num_fmt(&var.right)?)?,
+                        "unit" => {
+                            // whether sizes count chars, and whether they count KiB
+                            let val = match v.path.get_ident() {
+                                Some(v) if v == "bytes" => (false, false),
+                                Some(v) if v == "chars" => (true, false),
+                                Some(v) if v == "kib" => (false, true),
+                                _ => return Err(syn::Error::new_spanned(v, "unit must be `bytes`, `chars` or `kib`")),
+                            };
+                            set_once(&mut unit, key, val)?;
+                        }
+                        "endian" => {
+                            let val = match v.path.get_ident() {
+                                Some(v) if v == "little" => false,
+                                Some(v) if v == "big" => true,
+                                _ => return Err(syn::Error::new_spanned(v, "endian must be `big` or `little`")),
+                            };
+                            set_once(&mut big_endian, key, val)?;
+                        }
+                        "suffix" => {
+                            let Some(val) = v.path.get_ident() else {
+                                return Err(syn::Error::new_spanned(v, "suffix must be an identifier"));
+                            };
+                            set_once(&mut suffix, key, val.clone())?;
+                        }
+                        "repr" => set_once(&mut repr, key, vec![repr_hint(&var.right)?])?,
+                        _ => {
+                            field(&mut size_map, &var.left, &var.right)?;
+                            return Ok(());
+                        }
+                    }
+                    options.push(key.clone());
+                }
+                (Expr::Path(p), Expr::Lit(v)) if p.path.is_ident("pad") => {
+                    set_once(&mut pad, key_ident(p)?, pad_byte(v)?)?;
+                    options.push(key_ident(p)?.clone());
+                }
+                (Expr::Path(p), Expr::Tuple(t)) if p.path.is_ident("repr") => {
+                    let hints = t.elems.iter().map(repr_hint).collect::<Result<_>>()?;
+                    set_once(&mut repr, key_ident(p)?, hints)?;
+                    options.push(key_ident(p)?.clone());
+                }
+                (key, v @ (Expr::Lit(_) | Expr::Tuple(_) | Expr::Binary(_) | Expr::Paren(_) | Expr::Call(_))) => field(&mut size_map, key, v)?,
+                (_, v) => return Err(syn::Error::new_spanned(v, ERRMSG)),
+            }
+            Ok(())
+        };
+        for var in vars.into_iter() {
+            if let Err(e) = arg(var) {
+                match &mut errors {
+                    Some(acc) => acc.combine(e),
+                    None => errors = Some(e),
+                }
+            }
+        }
+        if let Some(e) = errors {
+            return Err(e);
+        }
+
+        // sizes given inline take precedence over the schema file
+        let schema_file = schema.map(|(file, full)| {
+            for (member, args) in file {
+                if !nested.contains_key(&member) && !skip.contains(&member) {
+                    size_map.entry(member).or_insert(args);
+                }
+            }
+            full
+        });
+        let any_typ = typ.as_ref().or(str_typ.as_ref()).or(vec_typ.as_ref());
+        if let (Some(shape), None) = (&typ_shape, any_typ) {
+            return Err(syn::Error::new_spanned(shape, "typ_shape needs a `typ` to apply to"));
+        }
+        // the shape supplies the generic arguments, so `typ` can't have any of its own
+        let generic = [&typ, &str_typ, &vec_typ].into_iter().flatten()
+            .find(|t| t.segments.iter().any(|s| !s.arguments.is_none()));
+        if let (Some(_), Some(typ)) = (&typ_shape, generic) {
+            return Err(syn::Error::new_spanned(typ, "`typ` can't have generic arguments alongside typ_shape"));
+        }
+        let vec_typ = vec_typ.or(typ.clone());
+        let typ = str_typ.or(typ);
+        if let Some(member) = tail.as_ref().filter(|m| size_map.contains_key(*m) || nested.contains_key(*m)) {
+            let msg = format!("field `{}` is the tail, which keeps its type, so it can't be given a size", member_name(member));
+            return Err(syn::Error::new_spanned(member, msg));
+        }
+        if let Some(member) = skip.iter().find(|m| size_map.contains_key(*m) || nested.contains_key(*m)) {
+            let msg = format!("field `{}` is both given a size and skipped", member_name(member));
+            return Err(syn::Error::new_spanned(member, msg));
+        }
+        if let Some(member) = nested.keys().find(|m| size_map.contains_key(*m)) {
+            let msg = format!("field `{}` specified more than once", member_name(member));
+            return Err(syn::Error::new_spanned(member, msg));
+        }
+        let on_overflow = on_overflow.unwrap_or_default();
+        // with `serde_with` the padding options configure the generated functions instead
+        let serde_pad = serde_pad || !serde_with && (pad.is_some() || right.is_some() || keep_pad.is_some()
+            || size_map.values().any(|f| f.right.is_some() || f.keep_pad.is_some() || f.pad.is_some()));
+        let no_rewrite = no_rewrite || serde_with;
+        let args = Args {
+            size_map, wildcard, typ, vec_typ, options, schema_file, gen_try_from, gen_len, gen_accessors, gen_capacity, as_char, gen_default, gen_schema, strict, gen_display, gen_debug, gen_static_asserts, gen_bytes, gen_bytes_ref, require_rename, validate_utf8, no_rewrite, gen_newtypes, gen_mutators, gen_write, enum_as_int, force, gen_str_eq, skip, serde_names, typ_shape, on_overflow, suffix, nested, repr, krate, tail, excluded, forwarded,
+            serde_pad, serde_with,
+            right: right.unwrap_or_default(),
+            zero_pad: zero_pad.unwrap_or_default(),
+            keep_pad: keep_pad.unwrap_or_default(),
+            chars: unit.is_some_and(|(chars, _)| chars),
+            kib: unit.is_some_and(|(_, kib)| kib),
+            big_endian: big_endian.unwrap_or_default(),
+            serde_len,
+            pad: pad.unwrap_or_default(),
+            ..Default::default()
+        };
+        args.validate_options()?;
+        Ok(args)
+    }
+}
+
+impl Args {
+    /// The key of the option `name` given outside `fields(...)`, to span errors about it
+    fn option(&self, name: &str) -> Option<&Ident> {
+        self.options.iter().find(|k| *k == name)
+    }
+
+    /// Reject combinations of options which contradict each other, or where one would
+    /// silently do nothing
+    fn validate_options(&self) -> Result<()> {
+        if let (true, Some(suffix)) = (self.enum_as_int, &self.suffix) {
+            let msg = "`enum_as_int` can't be combined with `suffix`, the integer can't always convert back";
+            return Err(syn::Error::new_spanned(suffix, msg));
+        }
+        if self.force && self.enum_as_int {
+            let msg = "`force` and `enum_as_int` both take over fields of types the macro doesn't know, pick one";
+            return Err(syn::Error::new(Span::call_site(), msg));
+        }
+        if self.no_rewrite {
+            let typed = self.size_map.values().any(|f| f.typ.is_some()) || self.typ.is_some() || self.vec_typ.is_some();
+            let conflict = [
+                (self.gen_try_from, "`gen_try_from`"), (self.gen_bytes, "`gen_bytes`"), (self.gen_bytes_ref, "`gen_bytes_ref`"),
+                (self.as_char, "`as_char`"),
+                (self.serde_pad, "padding"), (self.serde_len, "`serde_len`"), (self.suffix.is_some(), "`suffix`"),
+                (!self.nested.is_empty(), "`field: From -> To`"), (typed, "`typ`"), (self.gen_newtypes, "`gen_newtypes`"),
+                (self.gen_mutators, "`gen_mutators`"), (self.gen_write, "`gen_write`"), (self.enum_as_int, "`enum_as_int`"), (self.force, "`force`"),
+            ];
+            if let Some((_, name)) = conflict.iter().find(|(set, _)| *set) {
+                let keeps = if self.serde_with { "`serde_with`" } else { "`no_rewrite`" };
+                let msg = format!("{} needs the field types rewritten, which {} leaves alone", name, keeps);
+                return Err(syn::Error::new(Span::call_site(), msg));
+            }
+        }
+        let enabled = |feature: &str| match feature {
+            "accessors" => self.gen_accessors,
+            "capacity" => self.gen_capacity,
+            "debug" => self.gen_debug,
+            "display" => self.gen_display,
+            "mutators" => self.gen_mutators,
+            "str_eq" => self.gen_str_eq,
+            _ => self.gen_write,
+        };
+        if let Some((key, _)) = EXCLUDABLE.iter().filter(|f| !enabled(f)).find_map(|f| self.excluded.get(f)) {
+            let feature = key.to_string();
+            let msg = format!("`{}` excludes fields from `gen_{}`, which isn't given", key, &feature["exclude_".len()..]);
+            return Err(syn::Error::new_spanned(key, msg));
+        }
+        if let (true, Some(tail)) = (self.gen_bytes_ref, &self.tail) {
+            let msg = "`gen_bytes_ref` can't borrow a struct with a `tail`, which isn't part of its bytes";
+            return Err(syn::Error::new_spanned(tail, msg));
+        }
+        if let (false, Some(key)) = (self.gen_try_from, self.option("on_overflow")) {
+            let msg = "`on_overflow` only changes the `gen_try_from` setters, add `gen_try_from` or remove it";
+            return Err(syn::Error::new_spanned(key, msg));
+        }
+        if self.on_overflow == Overflow::Truncate {
+            let mut mins: Vec<_> = self.size_map.iter().filter_map(|(m, f)| Some((m, f.min.as_ref()?))).collect();
+            mins.sort_by_key(|(m, _)| member_name(m));
+            if let Some((member, min)) = mins.first() {
+                let msg = format!("field `{}` has a `min`, which `on_overflow=truncate` can't uphold, as truncating setters \
+                    never fail and so would take a value shorter than it", member_name(member));
+                return Err(syn::Error::new_spanned(min, msg));
+            }
+        }
+        if let (false, Some(key)) = (self.gen_bytes, self.option("endian")) {
+            let msg = "`endian` only changes `to_bytes` and `from_bytes`, add `gen_bytes` or remove it";
+            return Err(syn::Error::new_spanned(key, msg));
+        }
+        if self.as_char {
+            let padded = self.size_map.iter().find(|(_, f)| f.typ.is_none()
+                && matches!(&f.num, Size::Lit(n) if n.base10_digits() == "1")
+                && (f.right.is_some() || f.pad.is_some() || f.keep_pad.is_some()));
+            if let Some((member, _)) = padded {
+                let msg = format!("field `{}` becomes a `char` with `as_char`, which has no padding for `align`, `pad` or `trim` to apply to",
+                    member_name(member));
+                return Err(syn::Error::new_spanned(member, msg));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The single generic type argument of a path segment, e.g. `T` in `Vec<T>`
+fn generic_arg(seg: &PathSegment) -> Option<&Type> {
+    if let PathArguments::AngleBracketed(a) = &seg.arguments {
+        if let (1, Some(GenericArgument::Type(t))) = (a.args.len(), a.args.first()) {
+            return Some(t);
+        }
+    }
+    None
+}
+
+/// The key type of a `HashMap<K, V>` or `BTreeMap<K, V>`
+fn map_key(ty: &mut Type) -> Option<&mut Type> {
+    let Type::Path(p) = ty else { return None };
+    let seg = p.path.segments.last_mut().filter(|s| s.ident == "HashMap" || s.ident == "BTreeMap")?;
+    match &mut seg.arguments {
+        PathArguments::AngleBracketed(a) => match a.args.first_mut() {
+            Some(GenericArgument::Type(t)) => Some(t),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether `seg` is a `Cow` of `str`, e.g. `Cow<'a, str>`
+fn cow_str(seg: &PathSegment) -> bool {
+    let PathArguments::AngleBracketed(a) = &seg.arguments else { return false };
+    seg.ident == "Cow" && matches!(a.args.last(), Some(GenericArgument::Type(Type::Path(i))) if i.path.is_ident("str"))
+}
+
+/// Whether `tokens` mention the lifetime `'name`
+fn uses_lifetime(tokens: proc_macro2::TokenStream, name: &Ident) -> bool {
+    let mut tick = false;
+    for token in tokens {
+        match token {
+            proc_macro2::TokenTree::Group(g) if uses_lifetime(g.stream(), name) => return true,
+            proc_macro2::TokenTree::Ident(i) if tick && i == *name => return true,
+            proc_macro2::TokenTree::Punct(p) => {
+                tick = p.as_char() == '\'';
+                continue;
+            }
+            _ => {}
+        }
+        tick = false;
+    }
+    false
+}
+
+/// Remove the lifetime parameters of `item` which no field mentions any more, e.g. after a
+/// `Cow<'a, str>` was rewritten, as rustc rejects unused ones
+fn drop_unused_lifetimes(item: &mut ItemStruct) {
+    let fields = item.fields.to_token_stream();
+    let generics = item.generics.clone();
+    let used = |name: &Ident| {
+        let params = generics.params.iter().filter(|p| !matches!(p, GenericParam::Lifetime(l) if l.lifetime.ident == *name));
+        let predicates = generics.where_clause.iter().flat_map(|w| &w.predicates)
+            .filter(|p| !matches!(p, WherePredicate::Lifetime(l) if l.lifetime.ident == *name));
+        uses_lifetime(fields.clone(), name) || uses_lifetime(quote!(#(#params)* #(#predicates)*), name)
+    };
+    let params = std::mem::take(&mut item.generics.params);
+    item.generics.params = params.into_iter()
+        .filter(|p| !matches!(p, GenericParam::Lifetime(l) if !used(&l.lifetime.ident)))
+        .collect();
+    if let Some(w) = &mut item.generics.where_clause {
+        let params = &item.generics.params;
+        let predicates = std::mem::take(&mut w.predicates);
+        w.predicates = predicates.into_iter()
+            .filter(|p| match p {
+                WherePredicate::Lifetime(l) => params.iter().any(|p| matches!(p, GenericParam::Lifetime(k) if k.lifetime == l.lifetime)),
+                _ => true,
+            })
+            .collect();
+    }
+}
+
+/// Copy of `p` with the single generic argument of its last segment replaced by `arg`
+fn with_generic_arg(p: &TypePath, arg: Type) -> Type {
+    let mut p = p.clone();
+    if let Some(PathArguments::AngleBracketed(a)) = p.path.segments.last_mut().map(|s| &mut s.arguments) {
+        a.args[0] = GenericArgument::Type(arg);
+    }
+    Type::Path(p)
+}
+
+impl Args {
+    /// The field's `typ`, or the global one shaped by `typ_shape`, given the size `num` and for
+    /// vectors the element type `inner`
+    fn typ_type(&self, typ: Option<&Path>, num: &Size, inner: Option<&Type>) -> Option<Type> {
+        let shape = typ.is_none().then_some(self.typ_shape.as_ref()).flatten();
+        let global = if inner.is_some() { &self.vec_typ } else { &self.typ };
+        let typ = typ.or(global.as_ref())?;
+        Some(match (shape, inner) {
+            (Some(shape), _) => {
+                let args = shape.value().replace("{T}", &quote!(#inner).to_string()).replace("{}", &quote!(#num).to_string());
+                syn::parse_str(&format!("{}{}", quote!(#typ), args)).expect("typ_shape is checked while parsing")
+            }
+            (None, Some(inner)) => parse_quote!{#typ<#inner, #num>},
+            (None, None) => parse_quote!{#typ<#num>},
+        })
+    }
+
+    /// The padding of a field given `args`, falling back to the struct's
+    fn padding(&self, args: Option<&FieldArgs>) -> Padding {
+        Padding {
+            pad: args.and_then(|a| a.pad).unwrap_or(self.pad),
+            right: args.and_then(|a| a.right).unwrap_or(self.right),
+            keep: args.and_then(|a| a.keep_pad).unwrap_or(self.keep_pad),
+        }
+    }
+
+    /// Whether the string field `m` gets a zero padding `set_<field>_num`
+    fn zero_pad(&self, m: &Member) -> bool {
+        self.size_map.get(m).and_then(|a| a.zero_pad).unwrap_or(self.zero_pad)
+    }
+
+    /// The padding of the rewritten field `m`
+    fn field_padding(&self, m: &Member) -> Padding {
+        self.padding(self.size_map.get(m))
+    }
+
+    /// Call trimming the pad byte of the string field `m` from the side it's padded on, the end
+    /// unless it's aligned right, so data starting or ending with the pad character is kept
+    fn trim(&self, m: &Member) -> proc_macro2::TokenStream {
+        let Padding { pad, right, .. } = self.field_padding(m);
+        let pad = pad as char;
+        match right {
+            true => quote!(trim_start_matches(#pad)),
+            false => quote!(trim_end_matches(#pad)),
+        }
+    }
+
+    /// Point serde at the `serde_with` functions for the last field fixed, `field`, or report
+    /// it if it isn't a `String`
+    fn serde_with_attr(&mut self, field: &mut Field) {
+        let (Some(f), Some(module)) = (self.fixed.last(), &self.with_module) else {
+            return;
+        };
+        let string = matches!(&field.ty, Type::Path(p) if p.qself.is_none()
+            && p.path.segments.last().is_some_and(|s| s.ident == "String" && s.arguments.is_none()));
+        if f.kind != Kind::Str || !string {
+            let msg = "`serde_with` only handles `String` fields, leave this one out or `skip` it";
+            self.error(syn::Error::new_spanned(&field.ty, msg));
+            return;
+        }
+        let name = field_name(&f.member);
+        let ser = LitStr::new(&format!("{}::serialize_{}_fixed", module, name), Span::call_site());
+        let de = LitStr::new(&format!("{}::deserialize_{}_fixed", module, name), Span::call_site());
+        field.attrs.push(parse_quote!(#[serde(serialize_with = #ser, deserialize_with = #de)]));
+    }
+
+    /// `serialize_<field>_fixed` and `deserialize_<field>_fixed` for each `serde_with` field,
+    /// writing exactly its size padded with its pad byte and trimming that again when reading
+    fn serde_with_fns(&self, vis: &Visibility) -> proc_macro2::TokenStream {
+        let fns = self.fixed.iter().filter(|f| f.kind == Kind::Str).map(|f| {
+            let name = field_name(&f.member);
+            let (ser, de) = (format_ident!("serialize_{}_fixed", name), format_ident!("deserialize_{}_fixed", name));
+            let cap = f.chars.as_ref().unwrap_or(&f.num);
+            let Padding { pad, right, keep } = self.field_padding(&f.member);
+            let pad = pad as char;
+            let (len, trimmed_len, cfg) = (f.len(quote!(v)), f.len(quote!(trimmed)), self.cfg(&f.member));
+            let padded = if right {
+                quote!(padded.extend(::core::iter::repeat(#pad).take(#cap - got)); padded.push_str(v);)
+            } else {
+                quote!(padded.push_str(v); padded.extend(::core::iter::repeat(#pad).take(#cap - got));)
+            };
+            let trimmed = match (keep, right) {
+                (true, _) => quote!(&v[..]),
+                (false, true) => quote!(v.trim_start_matches(#pad)),
+                (false, false) => quote!(v.trim_end_matches(#pad)),
+            };
+            quote! {
+                #cfg
+                #vis fn #ser<S: ::serde::Serializer>(v: &str, s: S) -> ::core::result::Result<S::Ok, S::Error> {
+                    let got = #len;
+                    if got > #cap {
+                        let msg = ::core::format_args!("field `{}` holds {}, more than its size of {}", #name, got, #cap);
+                        return ::core::result::Result::Err(<S::Error as ::serde::ser::Error>::custom(msg));
+                    }
+                    let mut padded = ::std::string::String::with_capacity(v.len() + #cap - got);
+                    #padded
+                    s.serialize_str(&padded)
+                }
+
+                #cfg
+                #vis fn #de<'de, D: ::serde::Deserializer<'de>>(d: D) -> ::core::result::Result<::std::string::String, D::Error> {
+                    let v = <::std::string::String as ::serde::Deserialize>::deserialize(d)?;
+                    let trimmed = #trimmed;
+                    let got = #trimmed_len;
+                    if got > #cap {
+                        let msg = ::core::format_args!("field `{}` holds {}, more than its size of {}", #name, got, #cap);
+                        return ::core::result::Result::Err(<D::Error as ::serde::de::Error>::custom(msg));
+                    }
+                    ::core::result::Result::Ok(trimmed.to_owned())
+                }
+            }
+        });
+        quote!(#(#fns)*)
+    }
+
+    /// The fixed length string type of capacity `num`, the field's `typ` or the global one if
+    /// given, serialized padded to `chars` chars with `unit=chars`
+    fn str_type(&self, num: &Size, chars: Option<&Size>, typ: Option<&Path>, padding: Padding) -> Type {
+        match (self.typ_type(typ, num, None), &self.padded) {
+            (Some(ty), _) => ty,
+            (None, Some(padded)) => {
+                let Padding { pad, right, keep } = padding;
+                match chars {
+                    Some(chars) => parse_quote!{#padded<#num, #pad, #right, #keep, #chars>},
+                    None => parse_quote!{#padded<#num, #pad, #right, #keep>},
+                }
+            }
+            (None, None) => {
+                let arrayvec = self.arrayvec();
+                parse_quote!{#arrayvec::ArrayString<#num>}
+            }
+        }
+    }
+
+    /// The fixed length replacement for `ty`, if it's a type we know how to rewrite
+    fn replace(&self, ty: &Type, num: &Size, typ: Option<&Path>, padding: Padding) -> Result<Option<(Type, Kind)>> {
+        let as_char = self.as_char && typ.is_none() && matches!(num, Size::Lit(n) if n.base10_digits() == "1");
+        let Type::Path(p) = ty else { return Ok(None) };
+        let Some(seg) = p.path.segments.last() else { return Ok(None) };
+        let boxed_str = seg.ident == "Box" && matches!(generic_arg(seg), Some(Type::Path(i)) if i.path.is_ident("str"));
+        let string = (seg.ident == "String" && seg.arguments.is_none()) || boxed_str || cow_str(seg);
+        if string && as_char {
+            return Ok(Some((parse_quote!(char), Kind::Char)));
+        }
+        if string {
+            let (bytes, chars) = if self.chars { (num.chars(), Some(num)) } else { (num.clone(), None) };
+            return Ok(Some((self.str_type(&bytes, chars, typ, padding), Kind::Str)));
+        }
+        if seg.ident == "Vec" {
+            let Some(inner) = generic_arg(seg) else {
+                return Err(syn::Error::new_spanned(seg, "expected `Vec<T>` with a single type argument"));
+            };
+            let kind = match inner {
+                Type::Path(i) if i.path.is_ident("u8") => Kind::Bytes,
+                _ => Kind::Vec,
+            };
+            let ty = match (self.typ_type(typ, num, Some(inner)), &self.prefixed) {
+                (Some(ty), _) => ty,
+                (None, Some(prefixed)) if kind == Kind::Bytes => parse_quote!{#prefixed<#num>},
+                (None, None) if kind == Kind::Bytes => parse_quote!{[u8; #num]},
+                (None, _) => {
+                    let arrayvec = self.arrayvec();
+                    parse_quote!{#arrayvec::ArrayVec<#inner, #num>}
+                }
+            };
+            return Ok(Some((ty, kind)));
+        }
+        if seg.ident == "Bytes" && seg.arguments.is_none() {
+            let ty = match (self.typ_type(typ, num, Some(&parse_quote!(u8))), &self.prefixed) {
+                (Some(ty), _) => ty,
+                (None, Some(prefixed)) => parse_quote!{#prefixed<#num>},
+                (None, None) => parse_quote!{[u8; #num]},
+            };
+            return Ok(Some((ty, Kind::Bytes)));
+        }
+        if seg.ident == "Option" {
+            if let Some(inner @ Type::Path(i)) = generic_arg(seg) {
+                let kind = match i.path.segments.last() {
+                    Some(s) if s.ident == "String" => Kind::OptionStr,
+                    Some(s) if s.ident == "Vec" || s.ident == "Bytes" => Kind::OptionBytes,
+                    _ => return Ok(None),
+                };
+                if let Some((inner, Kind::Str | Kind::Bytes)) = self.replace(inner, num, typ, padding)? {
+                    return Ok(Some((with_generic_arg(p, inner), kind)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Path to the `arrayvec` crate used by generated code
+    fn arrayvec(&self) -> proc_macro2::TokenStream {
+        match &self.krate {
+            Some(krate) => quote!(#krate),
+            None => quote!(::arrayvec),
+        }
+    }
+
+    /// A `const` assertion for each fixed field of `item` that its size is non-zero, for sizes
+    /// naming a `const` which can't be checked while parsing
+    fn static_asserts(&self, item: &ItemStruct) -> proc_macro2::TokenStream {
+        // a free `const` can't use the struct's generic parameters
+        let asserts = self.fixed.iter().filter(|f| !matches!(f.chars.as_ref().unwrap_or(&f.num), Size::Param(_))).map(|f| {
+            let num = f.chars.as_ref().unwrap_or(&f.num);
+            let (msg, cfg) = (format!("field `{}` of `{}` has a size of zero", member_name(&f.member), item.ident), self.cfg(&f.member));
+            quote!(#cfg const _: () = ::core::assert!(#num > 0, #msg);)
+        });
+        quote!(#(#asserts)*)
+    }
+
+    /// Record an error to be emitted alongside the folded struct
+    fn error(&mut self, err: syn::Error) {
+        match &mut self.errors {
+            Some(acc) => acc.combine(err),
+            None => self.errors = Some(err),
+        }
+    }
+
+    /// The fixed length layout of every field of `item`, for options which need the whole
+    /// struct to have a known size
+    fn layout<'a>(&'a self, item: &'a ItemStruct) -> Result<Vec<(Member, Layout<'a>)>> {
+        let mut layout = Vec::new();
+        for (field, member) in item.fields.iter().zip(item.fields.members()) {
+            if self.tail.as_ref() == Some(&member) {
+                continue;
+            }
+            let l = match self.fixed.iter().find(|f| f.member == member) {
+                Some(f) if f.kind == Kind::Str => Layout::Str(&f.num),
+                Some(f) if f.kind == Kind::Bytes && matches!(f.ty, Type::Array(_)) => Layout::Bytes(&f.num),
+                Some(f) if f.kind == Kind::Bytes && f.ty_is(self.prefixed.as_ref()) => Layout::Prefixed(&f.num),
+                None if self.nested.contains_key(&member) => Layout::Nested(&field.ty),
+                None if is_numeric(&field.ty) => Layout::Num(&field.ty),
+                None if stacked_len(&field.ty).is_some() => Layout::Stacked(stacked_len(&field.ty).unwrap_or_default()),
+                // the fields of a `#[fixed]` stacked after this one aren't fixed yet
+                _ if item.attrs.iter().any(is_fixed_attr) => return Err(syn::Error::new_spanned(&field.ty, format!(
+                    "field `{}` doesn't have a fixed length yet, give the options needing every field on the last \
+                    `#[fixed]` of the struct", member_name(&member)))),
+                _ => return Err(syn::Error::new_spanned(&field.ty,
+                    format!("field `{}` doesn't have a fixed length", member_name(&member)))),
+            };
+            layout.push((member, l));
+        }
+        Ok(layout)
+    }
+
+    /// Inherent methods requested by the options, for the struct `item`
+    fn methods(&mut self, item: &ItemStruct, error_ty: &Ident) -> Option<proc_macro2::TokenStream> {
+        let vis = &item.vis;
+        let mut methods = Vec::new();
+        if self.gen_len || self.gen_bytes || self.gen_bytes_ref || self.tail.is_some() {
+            // recorded once the layout borrowing `self` is done with
+            let mut errors = Vec::new();
+            match self.layout(item) {
+                Ok(layout) => {
+                    let sizes = layout.iter().map(|(m, l)| self.cfg_len(m, l.len()));
+                    methods.push(quote! {
+                        /// Length in bytes of the struct's fixed length fields
+                        #vis const FIXED_LEN: usize = 0 #(+ #sizes)*;
+                    });
+                    let stacked = layout.iter().find(|(_, l)| matches!(l, Layout::Stacked(_)));
+                    if let (true, Some((m, _))) = (self.gen_bytes, stacked) {
+                        let msg = format!("`gen_bytes` needs to know how `{}` is padded, give its size in this `#[fixed]`, \
+                            not another stacked on the struct", member_name(m));
+                        errors.push(syn::Error::new_spanned(m, msg));
+                    } else if self.gen_bytes {
+                        methods.push(self.bytes_methods(vis, &layout, error_ty));
+                    }
+                    if let Some(tail) = &self.tail {
+                        let doc = format!("Length in bytes of the fixed header, the fields before `{}`", member_name(tail));
+                        let total_doc = format!("Length of the fixed header followed by `{}`", member_name(tail));
+                        methods.push(quote! {
+                            #[doc = #doc]
+                            #vis const fn header_len() -> usize {
+                                Self::FIXED_LEN
+                            }
+
+                            #[doc = #total_doc]
+                            #vis fn total_len(&self) -> usize {
+                                Self::FIXED_LEN + self.#tail.len()
+                            }
+                        });
+                    }
+                    if self.gen_bytes_ref {
+                        match bytes_ref_method(item, &layout) {
+                            Ok(method) => methods.push(method),
+                            Err(e) => errors.push(e),
+                        }
+                    }
+                }
+                Err(e) => errors.push(e),
+            }
+            for e in errors {
+                self.error(e);
+            }
+        }
+        if self.gen_schema {
+            let fields = || self.fixed.iter().filter(|f| f.kind != Kind::MapKey);
+            let names = fields().map(|f| field_name(&f.member));
+            let sizes = fields().map(|f| f.chars.as_ref().unwrap_or(&f.num));
+            let cfgs = fields().map(|f| self.cfg(&f.member));
+            methods.push(quote! {
+                /// Name and size of each fixed field, in declaration order
+                #vis const FIXED_FIELDS: &'static [(&'static str, usize)] = &[#(#cfgs (#names, #sizes)),*];
+            });
+        }
+        if self.gen_capacity {
+            for f in self.fixed.iter().filter(|f| f.kind != Kind::MapKey && !self.excluded("capacity", &f.member)) {
+                let method = match &f.member {
+                    Member::Named(i) => format_ident!("{}_capacity", i.unraw()),
+                    Member::Unnamed(i) => format_ident!("capacity_{}", i.index),
+                };
+                let doc = format!("Declared size of `{}`", member_name(&f.member));
+                let (size, cfg) = (f.chars.as_ref().unwrap_or(&f.num), self.cfg(&f.member));
+                methods.push(quote! {
+                    #cfg
+                    #[doc = #doc]
+                    #vis const fn #method() -> usize {
+                        #size
+                    }
+                });
+            }
+        }
+        if self.gen_accessors {
+            for f in self.fixed.iter().filter(|f| !self.excluded("accessors", &f.member)) {
+                let ident = &f.member;
+                let accessor = match ident {
+                    Member::Named(i) => i.clone(),
+                    Member::Unnamed(i) => format_ident!("get_{}", i.index),
+                };
+                let doc = format!("Borrow `{}`", member_name(ident));
+                let (ty, body) = match f.kind {
+                    Kind::Str => (quote!(&str), quote!(&self.#ident)),
+                    Kind::OptionStr => (quote!(::core::option::Option<&str>), quote!(self.#ident.as_ref().map(|s| s.as_str()))),
+                    Kind::Char => (quote!(char), quote!(self.#ident)),
+                    Kind::Bytes => (quote!(&[u8]), quote!(&self.#ident)),
+                    Kind::OptionBytes => (quote!(::core::option::Option<&[u8]>), quote!(self.#ident.as_ref().map(|b| &b[..]))),
+                    Kind::Vec | Kind::MapKey | Kind::StrVec => continue,
+                };
+                let cfg = self.cfg(ident);
+                methods.push(quote! {
+                    #cfg
+                    #[doc = #doc]
+                    #vis fn #accessor(&self) -> #ty {
+                        #body
+                    }
+                });
+            }
+        }
+        if self.gen_try_from {
+            for f in self.fixed.iter().filter(|f| f.kind == Kind::Str) {
+                let ident = &f.member;
+                let name = member_name(ident);
+                let setter = match ident {
+                    Member::Named(i) => format_ident!("set_{}", i.unraw()),
+                    Member::Unnamed(i) => format_ident!("set_{}", i.index),
+                };
+                let cfg = self.cfg(ident);
+                methods.push(match (self.on_overflow, &f.chars) {
+                    (Overflow::Error, _) => {
+                        let doc = format!("Set `{}`, failing if `v` doesn't fit", name);
+                        let convert = f.try_convert(quote!(v), error_ty);
+                        quote! {
+                            #cfg
+                            #[doc = #doc]
+                            #vis fn #setter(&mut self, v: &str) -> ::core::result::Result<(), #error_ty> {
+                                self.#ident = #convert;
+                                ::core::result::Result::Ok(())
+                            }
+                        }
+                    }
+                    (Overflow::Truncate, Some(chars)) => {
+                        let doc = format!("Set `{}`, truncating `v` to {} chars", name, quote!(#chars));
+                        quote! {
+                            #cfg
+                            #[doc = #doc]
+                            #vis fn #setter(&mut self, v: &str) {
+                                let end = v.char_indices().nth(#chars).map_or(v.len(), |(i, _)| i);
+                                self.#ident.clear();
+                                // fits, but `typ`s like `heapless::String` still return a `Result`
+                                let _ = self.#ident.push_str(&v[..end]);
+                            }
+                        }
+                    }
+                    (Overflow::Truncate, None) => {
+                        let doc = format!("Set `{}`, truncating `v` to the capacity", name);
+                        quote! {
+                            #cfg
+                            #[doc = #doc]
+                            #vis fn #setter(&mut self, v: &str) {
+                                let mut end = v.len().min(self.#ident.capacity());
+                                while !v.is_char_boundary(end) {
+                                    end -= 1;
+                                }
+                                self.#ident.clear();
+                                // fits, but `typ`s like `heapless::String` still return a `Result`
+                                let _ = self.#ident.push_str(&v[..end]);
+                            }
+                        }
+                    }
+                });
+            }
+        }
+        for f in self.fixed.iter().filter(|f| f.kind == Kind::Str && self.zero_pad(&f.member)) {
+            let ident = &f.member;
+            let setter = match ident {
+                Member::Named(i) => format_ident!("set_{}_num", i.unraw()),
+                Member::Unnamed(i) => format_ident!("set_{}_num", i.index),
+            };
+            let doc = format!("Set `{}` to `v` right aligned and zero padded to its size, failing if it doesn't fit",
+                member_name(ident));
+            let (num, cap) = (&f.num, f.chars.as_ref().unwrap_or(&f.num));
+            let (convert, overflow, cfg) = (f.try_convert(quote!(&padded), error_ty), f.overflow(error_ty), self.cfg(ident));
+            let arrayvec = self.arrayvec();
+            let len = f.len(quote!(s));
+            methods.push(quote! {
+                #cfg
+                #[doc = #doc]
+                #vis fn #setter(&mut self, v: impl ::core::fmt::Display) -> ::core::result::Result<(), #error_ty> {
+                    /// `v` formatted as far as it fits, with the length it took in all
+                    struct Digits<const CAP: usize>(#arrayvec::ArrayString<CAP>, usize);
+                    impl<const CAP: usize> ::core::fmt::Write for Digits<CAP> {
+                        fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+                            self.1 += #len;
+                            let _ = self.0.try_push_str(s);
+                            ::core::fmt::Result::Ok(())
+                        }
+                    }
+                    let mut digits = Digits::<#num>(#arrayvec::ArrayString::new(), 0);
+                    let _ = ::core::fmt::Write::write_fmt(&mut digits, ::core::format_args!("{}", v));
+                    let got = digits.1;
+                    if got > #cap {
+                        return ::core::result::Result::Err(#overflow);
+                    }
+                    // zeros go after the sign, so `-42` becomes `-000000042`
+                    let (sign, rest) = match digits.0.starts_with(['-', '+']) {
+                        true => digits.0.split_at(1),
+                        false => ("", digits.0.as_str()),
+                    };
+                    let mut padded = #arrayvec::ArrayString::<#num>::new();
+                    padded.push_str(sign);
+                    for _ in got..#cap {
+                        padded.push('0');
+                    }
+                    padded.push_str(rest);
+                    self.#ident = #convert;
+                    ::core::result::Result::Ok(())
+                }
+            });
+        }
+        for (m, ty, int) in &self.enums {
+            let name = member_name(m);
+            let field = field_name(m);
+            let (getter, setter) = match m {
+                Member::Named(i) => (i.clone(), format_ident!("set_{}", i.unraw())),
+                Member::Unnamed(i) => (format_ident!("get_{}", i.index), format_ident!("set_{}", i.index)),
+            };
+            let doc = format!("`{}` as `{}`, converted with its `TryFrom<{}>`", name, quote!(#ty), quote!(#int));
+            let (set_doc, cfg) = (format!("Store the discriminant of `v` in `{}`", name), self.cfg(m));
+            methods.push(quote! {
+                #cfg
+                #[doc = #doc]
+                #vis fn #getter(&self) -> ::core::result::Result<#ty, #error_ty> {
+                    <#ty as ::core::convert::TryFrom<#int>>::try_from(self.#m)
+                        .map_err(|_| #error_ty::Discriminant { field: #field, value: self.#m as u64 })
+                }
+
+                #cfg
+                #[doc = #set_doc]
+                #vis fn #setter(&mut self, v: #ty) {
+                    self.#m = v as #int;
+                }
+            });
+        }
+        if self.gen_mutators {
+            for f in self.fixed.iter().filter(|f| f.kind == Kind::Str && !self.excluded("mutators", &f.member)) {
+                let (ident, len, overflow) = (&f.member, f.len(quote!(v)), f.overflow(error_ty));
+                let cap = f.chars.as_ref().unwrap_or(&f.num);
+                let method = match ident {
+                    Member::Named(i) => format_ident!("{}_try_push_str", i.unraw()),
+                    Member::Unnamed(i) => format_ident!("try_push_str_{}", i.index),
+                };
+                let doc = format!("Append `v` to `{}`, failing if the result doesn't fit", member_name(ident));
+                let cfg = self.cfg(ident);
+                methods.push(quote! {
+                    #cfg
+                    #[doc = #doc]
+                    #vis fn #method(&mut self, v: &str) -> ::core::result::Result<(), #error_ty> {
+                        let got = { let v: &str = &self.#ident; #len } + #len;
+                        if got > #cap {
+                            return ::core::result::Result::Err(#overflow);
+                        }
+                        self.#ident.try_push_str(v).map_err(|_| #overflow)
+                    }
+                });
+            }
+        }
+        if self.gen_write {
+            for f in self.fixed.iter().filter(|f| f.kind == Kind::Str && !self.excluded("write", &f.member)) {
+                let ident = &f.member;
+                let method = match ident {
+                    Member::Named(i) => format_ident!("{}_writer", i.unraw()),
+                    Member::Unnamed(i) => format_ident!("writer_{}", i.index),
+                };
+                let doc = format!("A `fmt::Write` appending to `{}`, failing with `fmt::Error` once it doesn't fit",
+                    member_name(ident));
+                // the `ArrayString` under any wrapper, or the field if it has a `typ` of its own
+                let typed = self.size_map.get(ident).and_then(|a| a.typ.as_ref()).or(self.typ.as_ref()).is_some();
+                let body = match typed {
+                    true => quote!(&mut self.#ident),
+                    false => {
+                        let (arrayvec, num) = (self.arrayvec(), &f.num);
+                        quote! {
+                            let s: &mut #arrayvec::ArrayString<#num> = &mut self.#ident;
+                            s
+                        }
+                    }
+                };
+                // with `unit=chars` the capacity in bytes is the worst case, so count the chars
+                let body = match &f.chars {
+                    Some(chars) => quote! {
+                        /// Appends to the field while it holds at most `CHARS` chars
+                        struct Chars<'a, T, const CHARS: usize>(&'a mut T);
+                        impl<T, const CHARS: usize> ::core::fmt::Write for Chars<'_, T, CHARS>
+                        where
+                            T: ::core::fmt::Write + ::core::ops::Deref<Target = str>,
+                        {
+                            fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+                                if self.0.chars().count() + s.chars().count() > CHARS {
+                                    return ::core::result::Result::Err(::core::fmt::Error);
+                                }
+                                self.0.write_str(s)
+                            }
+                        }
+                        let writer = { #body };
+                        Chars::<_, #chars>(writer)
+                    },
+                    None => body,
+                };
+                let cfg = self.cfg(ident);
+                methods.push(quote! {
+                    #cfg
+                    #[doc = #doc]
+                    #vis fn #method(&mut self) -> impl ::core::fmt::Write + '_ {
+                        #body
+                    }
+                });
+            }
+        }
+        if self.gen_str_eq {
+            for f in self.fixed.iter().filter(|f| !self.excluded("str_eq", &f.member)) {
+                let ident = &f.member;
+                let trim = self.trim(ident);
+                let method = match ident {
+                    Member::Named(i) => format_ident!("{}_eq", i.unraw()),
+                    Member::Unnamed(i) => format_ident!("eq_{}", i.index),
+                };
+                let doc = format!("Whether `{}` equals `other`, ignoring padding", member_name(ident));
+                let (ty, body) = match f.kind {
+                    Kind::Str => (quote!(&str), quote!(self.#ident.#trim == other)),
+                    Kind::OptionStr => (
+                        quote!(::core::option::Option<&str>),
+                        quote!(self.#ident.as_ref().map(|s| s.#trim) == other),
+                    ),
+                    Kind::Char => (quote!(&str), quote! {{
+                        let mut chars = other.chars();
+                        chars.next() == ::core::option::Option::Some(self.#ident) && chars.next().is_none()
+                    }}),
+                    _ => continue,
+                };
+                let cfg = self.cfg(ident);
+                methods.push(quote! {
+                    #cfg
+                    #[doc = #doc]
+                    #vis fn #method(&self, other: #ty) -> bool {
+                        #body
+                    }
+                });
+            }
+        }
+        let mut checks: Vec<_> = self.fixed.iter().filter_map(|f| {
+            // the strings of a `Vec<String>` carry its `min`
+            let strings = f.inner.as_deref().unwrap_or(f);
+            let (m, min) = (&f.member, strings.min.as_ref().filter(|_| f.kind != Kind::Char)?);
+            let field = field_name(m);
+            let len = strings.len(quote!(v));
+            let check = quote! {
+                let len = #len;
+                if len < #min {
+                    return ::core::result::Result::Err(#error_ty::TooShort { field: #field, min: #min, len });
+                }
+            };
+            Some((m, match f.kind {
+                Kind::OptionStr => quote!(if let ::core::option::Option::Some(v) = &self.#m { #check }),
+                Kind::MapKey => quote!(for v in self.#m.keys() { #check }),
+                Kind::StrVec => quote!(for v in self.#m.iter() { #check }),
+                _ => quote!({ let v = &self.#m; #check }),
+            }))
+        }).collect();
+        if self.no_rewrite {
+            checks.extend(self.fixed.iter().map(|f| (&f.member, self.cap_check(f, error_ty))));
+        }
+        if self.validate_utf8 {
+            checks.extend(self.fixed.iter().filter(|f| f.kind == Kind::Bytes && matches!(f.ty, Type::Array(_)))
+                .map(|f| (&f.member, self.utf8_check(&f.member, { let m = &f.member; quote!(&self.#m[..]) }, error_ty))));
+        }
+        let checks: Vec<_> = checks.into_iter().map(|(m, check)| match self.cfgs.contains_key(m) {
+            true => {
+                let cfg = self.cfg(m);
+                quote!(#cfg { #check })
+            }
+            false => check,
+        }).collect();
+        if !checks.is_empty() {
+            methods.push(quote! {
+                /// Check that each field with a `min` is at least that long, with `no_rewrite` that
+                /// each fixed field fits its size, and with `validate_utf8` that byte arrays hold
+                /// text
+                #vis fn validate(&self) -> ::core::result::Result<(), #error_ty> {
+                    #(#checks)*
+                    ::core::result::Result::Ok(())
+                }
+            });
+        }
+        if methods.is_empty() {
+            return None;
+        }
+        let name = &item.ident;
+        let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+        Some(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #(#methods)*
+            }
+        })
+    }
+
+    /// Return `error_ty::Overflow` if the field `f`, left alone by `no_rewrite`, is longer than
+    /// its size
+    fn cap_check(&self, f: &Fixed, error_ty: &Ident) -> proc_macro2::TokenStream {
+        let check = |f: &Fixed, len| {
+            let (cap, overflow) = (f.chars.as_ref().unwrap_or(&f.num), f.overflow(error_ty));
+            quote! {
+                let got = #len;
+                if got > #cap {
+                    return ::core::result::Result::Err(#overflow);
+                }
+            }
+        };
+        let m = &f.member;
+        match (f.kind, f.inner.as_deref()) {
+            (Kind::StrVec, Some(strings)) => {
+                let (count, each) = (check(f, quote!(v.len())), check(strings, strings.len(quote!(v))));
+                quote!({ let v = &self.#m; #count } for v in self.#m.iter() { #each })
+            }
+            (Kind::OptionStr, _) => {
+                let check = check(f, f.len(quote!(v)));
+                quote!(if let ::core::option::Option::Some(v) = &self.#m { #check })
+            }
+            (Kind::OptionBytes, _) => {
+                let check = check(f, quote!(v.len()));
+                quote!(if let ::core::option::Option::Some(v) = &self.#m { #check })
+            }
+            (Kind::MapKey, _) => {
+                let check = check(f, f.len(quote!(v)));
+                quote!(for v in self.#m.keys() { #check })
+            }
+            (Kind::Str, _) => {
+                let check = check(f, f.len(quote!(v)));
+                quote!({ let v = &self.#m; #check })
+            }
+            _ => {
+                let check = check(f, quote!(v.len()));
+                quote!({ let v = &self.#m; #check })
+            }
+        }
+    }
+
+    /// Return `error_ty::Utf8` if the bytes `v` of the field `m` aren't UTF-8 up to the first pad
+    /// byte
+    fn utf8_check(&self, m: &Member, v: proc_macro2::TokenStream, error_ty: &Ident) -> proc_macro2::TokenStream {
+        let (pad, field) = (self.field_padding(m).pad, field_name(m));
+        quote! {{
+            let v: &[u8] = #v;
+            let end = v.iter().position(|b| *b == #pad).unwrap_or(v.len());
+            if ::core::str::from_utf8(&v[..end]).is_err() {
+                return ::core::result::Result::Err(#error_ty::Utf8 { field: #field });
+            }
+        }}
+    }
+
+    /// `to_bytes` and `from_bytes` writing each field of `layout` at its offset
+    fn bytes_methods(&self, vis: &Visibility, layout: &[(Member, Layout)], error_ty: &Ident) -> proc_macro2::TokenStream {
+        let (to, from) = match self.big_endian {
+            true => (quote!(to_be_bytes), quote!(from_be_bytes)),
+            false => (quote!(to_le_bytes), quote!(from_le_bytes)),
+        };
+        let mut offset = quote!(0);
+        let (mut writes, mut reads) = (Vec::new(), Vec::new());
+        for (m, l) in layout {
+            let (at, len) = (offset.clone(), l.len());
+            let field = field_name(m);
+            let span = quote!(#at..#at + #len);
+            let f = self.fixed.iter().find(|f| f.member == *m);
+            let (write, read) = match (l, f) {
+                (Layout::Str(num), Some(f)) => {
+                    let Padding { pad, right, keep } = self.field_padding(m);
+                    let trim = match (keep, right) {
+                        (true, _) => quote!(),
+                        (false, true) => quote!(.trim_start_matches(#pad as char)),
+                        (false, false) => quote!(.trim_end_matches(#pad as char)),
+                    };
+                    let convert = f.try_convert(quote! {
+                        ::core::str::from_utf8(&b[#span])
+                            .map_err(|_| #error_ty::Utf8 { field: #field })?
+                            #trim
+                    }, error_ty);
+                    let write = match right {
+                        true => quote! {
+                            let s: &str = &self.#m;
+                            let start = #at + #num - s.len();
+                            out[#at..start].fill(#pad);
+                            out[start..#at + #num].copy_from_slice(s.as_bytes());
+                        },
+                        false => quote! {
+                            let s: &str = &self.#m;
+                            out[#at..#at + s.len()].copy_from_slice(s.as_bytes());
+                            out[#at + s.len()..#at + #num].fill(#pad);
+                        },
+                    };
+                    (write, convert)
+                }
+                (Layout::Bytes(num), _) => {
+                    let check = self.validate_utf8.then(|| self.utf8_check(m, quote!(&a[..]), error_ty));
+                    (
+                        quote!(out[#span].copy_from_slice(&self.#m);),
+                        quote!({ let mut a = [0u8; #num]; a.copy_from_slice(&b[#span]); #check a }),
+                    )
+                }
+                (Layout::Prefixed(num), Some(f)) => {
+                    let (ty, overflow) = (&f.ty, f.overflow(error_ty));
+                    let start = quote!(#at + ::core::mem::size_of::<u32>());
+                    (quote! {
+                        let v: &[u8] = &self.#m;
+                        out[#at..#start].copy_from_slice(&(v.len() as u32).#to());
+                        out[#start..#start + v.len()].copy_from_slice(v);
+                    }, quote! {{
+                        let mut len = [0u8; ::core::mem::size_of::<u32>()];
+                        len.copy_from_slice(&b[#at..#start]);
+                        let got = u32::#from(len) as usize;
+                        if got > #num {
+                            return ::core::result::Result::Err(#overflow);
+                        }
+                        let mut a: #ty = ::core::default::Default::default();
+                        a.extend(b[#start..#start + got].iter().copied());
+                        a
+                    }})
+                }
+                (Layout::Num(ty), _) => (
+                    quote!(out[#span].copy_from_slice(&self.#m.#to());),
+                    quote!({ let mut a = [0u8; #len]; a.copy_from_slice(&b[#span]); <#ty>::#from(a) }),
+                ),
+                (Layout::Nested(ty), _) => (
+                    quote!(out[#span].copy_from_slice(&self.#m.to_bytes());),
+                    quote! {{
+                        let mut a = [0u8; #len];
+                        a.copy_from_slice(&b[#span]);
+                        <#ty>::from_bytes(&a).map_err(|_| #error_ty::Nested { field: #field })?
+                    }},
+                ),
+                (Layout::Str(_) | Layout::Prefixed(_), None) | (Layout::Stacked(_), _) => continue,
+            };
+            let cfg = self.cfg(m);
+            writes.push(quote!(#cfg { #write }));
+            reads.push(quote!(#cfg #m: #read));
+            let len = self.cfg_len(m, len);
+            offset = quote!(#at + #len);
+        }
+        if let Some(tail) = &self.tail {
+            let cfg = self.cfg(tail);
+            reads.push(quote!(#cfg #tail: ::core::default::Default::default()));
+        }
+        quote! {
+            /// The fixed length fields laid out in declaration order, strings padded to their
+            /// capacity
+            #vis fn to_bytes(&self) -> [u8; Self::FIXED_LEN] {
+                let mut out = [0u8; Self::FIXED_LEN];
+                #(#writes)*
+                out
+            }
+
+            /// Read the fields back from the layout written by `to_bytes`
+            #vis fn from_bytes(b: &[u8; Self::FIXED_LEN]) -> ::core::result::Result<Self, #error_ty> {
+                ::core::result::Result::Ok(Self { #(#reads),* })
+            }
+        }
+    }
+
+    /// `TryFrom<original>` for the fixed sibling struct `item`, failing with `error_ty`
+    fn conversions(&self, original: &ItemStruct, item: &ItemStruct, error_ty: &Ident) -> proc_macro2::TokenStream {
+        let members = item.fields.members();
+        let values = item.fields.members().map(|m| {
+            if self.nested.contains_key(&m) {
+                let field = field_name(&m);
+                return quote! {
+                    ::core::convert::TryFrom::try_from(v.#m).map_err(|_| #error_ty::Nested { field: #field })?
+                };
+            }
+            let Some(f) = self.fixed.iter().find(|f| f.member == m) else {
+                return quote!(v.#m);
+            };
+            let (ty, num, overflow) = (&f.ty, &f.num, f.overflow(error_ty));
+            match (f.kind, ty) {
+                (Kind::Str, _) => f.try_convert(quote!(&v.#m), error_ty),
+                (Kind::OptionStr, _) => {
+                    let convert = f.try_convert(quote!(&s), error_ty);
+                    quote! {
+                        match v.#m {
+                            ::core::option::Option::Some(s) => ::core::option::Option::Some(#convert),
+                            ::core::option::Option::None => ::core::option::Option::None,
+                        }
+                    }
+                }
+                (Kind::Char, _) => {
+                    let field = field_name(&m);
+                    quote! {{
+                        let mut chars = v.#m.chars();
+                        match (chars.next(), chars.next()) {
+                            (::core::option::Option::Some(c), ::core::option::Option::None) => c,
+                            (::core::option::Option::None, _) => {
+                                return ::core::result::Result::Err(#error_ty::TooShort { field: #field, min: 1, len: 0 });
+                            }
+                            _ => {
+                                let got = v.#m.chars().count();
+                                return ::core::result::Result::Err(#overflow);
+                            }
+                        }
+                    }}
+                }
+                (Kind::Bytes | Kind::Vec, _) => f.collect(quote!(v.#m), ty, error_ty),
+                (Kind::OptionBytes, _) => {
+                    let inner = match ty {
+                        Type::Path(p) => p.path.segments.last().and_then(generic_arg),
+                        _ => None,
+                    };
+                    let inner = inner.unwrap_or(ty);
+                    let collect = f.collect(quote!(b), inner, error_ty);
+                    quote! {
+                        match v.#m {
+                            ::core::option::Option::Some(b) => ::core::option::Option::Some(#collect),
+                            ::core::option::Option::None => ::core::option::Option::None,
+                        }
+                    }
+                }
+                (Kind::MapKey, _) => {
+                    let convert = f.try_convert(quote!(&k), error_ty);
+                    quote! {
+                        ::core::iter::IntoIterator::into_iter(v.#m)
+                            .map(|(k, x)| ::core::result::Result::Ok((#convert, x)))
+                            .collect::<::core::result::Result<_, #error_ty>>()?
+                    }
+                }
+                (Kind::StrVec, _) => {
+                    let convert = f.inner.as_ref().map(|inner| inner.try_convert(quote!(&s), error_ty));
+                    quote! {{
+                        let items = v.#m;
+                        let got = items.len();
+                        if got > #num {
+                            return ::core::result::Result::Err(#overflow);
+                        }
+                        let mut a: #ty = ::core::default::Default::default();
+                        for s in items {
+                            a.push(#convert);
+                        }
+                        a
+                    }}
+                }
+            }
+        });
+        let (name, fixed_name) = (&original.ident, &item.ident);
+        // the fixed struct may have dropped lifetimes the original still has
+        let (impl_generics, ty_generics, where_clause) = original.generics.split_for_impl();
+        let (_, fixed_generics, _) = item.generics.split_for_impl();
+        // fields are moved out before borrowing them, which also works for packed structs
+        let back = item.fields.members().zip(&original.fields).map(|(m, field)| {
+            if self.nested.contains_key(&m) {
+                return quote!(::core::convert::From::from(v.#m));
+            }
+            let cow = matches!(&field.ty, Type::Path(p) if p.path.segments.last().is_some_and(cow_str));
+            match self.fixed.iter().find(|f| f.member == m).map(|f| f.kind) {
+                // a `Cow` can only borrow for its own lifetime
+                Some(Kind::Str) if cow => quote!({ let s = v.#m; ::core::convert::From::from(s.as_str().to_owned()) }),
+                Some(Kind::Str) => quote!({ let s = v.#m; ::core::convert::From::from(s.as_str()) }),
+                Some(Kind::OptionStr) => quote!(v.#m.map(|s| ::core::convert::From::from(s.as_str()))),
+                Some(Kind::Char) if cow => quote!(::core::convert::From::from(v.#m.to_string())),
+                Some(Kind::Char) => quote!(::core::convert::From::from(v.#m.encode_utf8(&mut [0; 4]) as &str)),
+                Some(Kind::Bytes) => quote!({ let b = v.#m; ::core::convert::From::from(b.to_vec()) }),
+                Some(Kind::OptionBytes) => quote!(v.#m.map(|b| ::core::convert::From::from(b.to_vec()))),
+                Some(Kind::Vec) => quote!(::core::iter::IntoIterator::into_iter(v.#m).collect()),
+                Some(Kind::StrVec) => quote! {
+                    ::core::iter::IntoIterator::into_iter(v.#m).map(|s| ::core::convert::From::from(s.as_str())).collect()
+                },
+                Some(Kind::MapKey) => quote! {
+                    ::core::iter::IntoIterator::into_iter(v.#m).map(|(k, x)| (::core::convert::From::from(k.as_str()), x)).collect()
+                },
+                None => quote!(v.#m),
+            }
+        });
+        let members_back = item.fields.members();
+        let cfgs: Vec<_> = item.fields.members().map(|m| self.cfg(&m)).collect();
+        quote! {
+            impl #impl_generics ::core::convert::TryFrom<#name #ty_generics> for #fixed_name #fixed_generics #where_clause {
+                type Error = #error_ty;
+
+                fn try_from(v: #name #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                    ::core::result::Result::Ok(Self { #(#cfgs #members: #values),* })
+                }
+            }
+
+            impl #impl_generics ::core::convert::From<#fixed_name #fixed_generics> for #name #ty_generics #where_clause {
+                fn from(v: #fixed_name #fixed_generics) -> Self {
+                    Self { #(#cfgs #members_back: #back),* }
+                }
+            }
+        }
+    }
+
+    /// `Display` for `item` writing its fixed string fields with the padding trimmed, separated
+    /// by spaces
+    fn display_impl(&self, item: &ItemStruct) -> proc_macro2::TokenStream {
+        let strings = self.fixed.iter()
+            .filter(|f| matches!(f.kind, Kind::Str | Kind::OptionStr | Kind::Char) && !self.excluded("display", &f.member));
+        // with a `#[cfg]` field the first one written is only known at run time
+        let cfg_sep = strings.clone().any(|f| self.cfgs.contains_key(&f.member));
+        let writes: Vec<_> = strings.enumerate().map(|(i, f)| {
+            let m = &f.member;
+            let trim = self.trim(m);
+            let sep = match (cfg_sep, i) {
+                (true, _) => quote!(f.write_str(sep)?; sep = " ";),
+                (false, 0) => quote!(),
+                (false, _) => quote!(f.write_str(" ")?;),
+            };
+            let cfg = self.cfg(m);
+            let write = match f.kind {
+                Kind::Str => quote!(#sep f.write_str(self.#m.#trim)?;),
+                Kind::OptionStr => quote! {
+                    #sep
+                    if let ::core::option::Option::Some(s) = &self.#m {
+                        f.write_str(s.#trim)?;
+                    }
+                },
+                _ => quote!(#sep ::core::fmt::Write::write_char(f, self.#m)?;),
+            };
+            if cfg_sep { quote!(#cfg { #write }) } else { write }
+        }).collect();
+        let (start, end) = match cfg_sep {
+            true => (quote!(let mut sep = "";), quote!(let _ = sep;)),
+            false => (quote!(), quote!()),
+        };
+        let name = &item.ident;
+        let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+        quote! {
+            impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    #start
+                    #(#writes)*
+                    #end
+                    ::core::result::Result::Ok(())
+                }
+            }
+        }
+    }
+
+    /// `Debug` for `item` writing each fixed string, byte or vector field as its contents followed
+    /// by `(cap=N)`, and the other fields as usual
+    fn debug_impl(&self, item: &ItemStruct) -> proc_macro2::TokenStream {
+        let fields = item.fields.members().filter(|m| !self.excluded("debug", m)).map(|m| {
+            let value = match self.fixed.iter().find(|f| f.member == m) {
+                Some(f @ Fixed { kind: Kind::Str | Kind::OptionStr | Kind::Bytes | Kind::OptionBytes | Kind::Vec | Kind::StrVec, .. }) => {
+                    let cap = f.chars.as_ref().unwrap_or(&f.num);
+                    let contents = match f.kind {
+                        Kind::Str => quote!({ let s: &str = &self.#m; s }),
+                        Kind::OptionStr => quote!(self.#m.as_ref().map(|s| -> &str { s })),
+                        Kind::OptionBytes => quote!(self.#m.as_ref().map(|b| &b[..])),
+                        _ => quote!(&self.#m[..]),
+                    };
+                    quote!(&::core::format_args!("{:?} (cap={})", #contents, #cap))
+                }
+                _ => quote!(&self.#m),
+            };
+            let cfg = self.cfg(&m);
+            match &m {
+                Member::Named(i) => {
+                    let name = i.unraw().to_string();
+                    quote!(#cfg d.field(#name, #value);)
+                }
+                Member::Unnamed(_) => quote!(#cfg d.field(#value);),
+            }
+        });
+        let name = &item.ident;
+        let start = match &item.fields {
+            syn::Fields::Named(_) => quote!(debug_struct),
+            _ => quote!(debug_tuple),
+        };
+        let label = name.unraw().to_string();
+        // bound type parameters like `#[derive(Debug)]` would
+        let mut generics = item.generics.clone();
+        let params: Vec<_> = generics.type_params().map(|t| t.ident.clone()).collect();
+        let where_clause = generics.make_where_clause();
+        for t in params {
+            where_clause.predicates.push(parse_quote!(#t: ::core::fmt::Debug));
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        quote! {
+            impl #impl_generics ::core::fmt::Debug for #name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    let mut d = f.#start(#label);
+                    #(#fields)*
+                    d.finish()
+                }
+            }
+        }
+    }
+
+    /// `Default` for `item` which zeroes byte arrays, whatever their length, and defaults the rest
+    fn default_impl(&self, item: &ItemStruct) -> proc_macro2::TokenStream {
+        let members = item.fields.members();
+        let values = item.fields.members().map(|m| match self.fixed.iter().find(|f| f.member == m) {
+            Some(Fixed { kind: Kind::Bytes, ty: Type::Array(_), num, .. }) if !self.no_rewrite => quote!([0u8; #num]),
+            _ => quote!(::core::default::Default::default()),
+        });
+        let cfgs = item.fields.members().map(|m| self.cfg(&m));
+        let name = &item.ident;
+        // bound type parameters like `#[derive(Default)]` would
+        let mut generics = item.generics.clone();
+        let params: Vec<_> = generics.type_params().map(|t| t.ident.clone()).collect();
+        let where_clause = generics.make_where_clause();
+        for t in params {
+            where_clause.predicates.push(parse_quote!(#t: ::core::default::Default));
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        quote! {
+            impl #impl_generics ::core::default::Default for #name #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self { #(#cfgs #members: #values),* }
+                }
+            }
+        }
+    }
+
+    /// Rewrite the `Vec<String>` `field` given `field=(N, M)`, fixing both the count and the
+    /// strings
+    fn fix_str_vec(&mut self, member: Member, field: &mut Field, args: FieldArgs) {
+        let padding = self.padding(Some(&args));
+        let p = match &field.ty {
+            Type::Path(p) if p.path.segments.last().is_some_and(|seg| seg.ident == "Vec"
+                && matches!(generic_arg(seg), Some(Type::Path(i)) if i.path.is_ident("String"))) => p,
+            ty => {
+                let msg = format!("field `{}` was given two sizes, which is only supported for `Vec<String>`", member_name(&member));
+                return self.error(syn::Error::new_spanned(ty, msg));
+            }
+        };
+        let Some(inner) = args.inner else { return };
+        let (num, chars) = if self.chars { (inner.chars(), Some(&inner)) } else { (inner.clone(), None) };
+        let str_ty = self.str_type(&num, chars, None, padding);
+        let vec_ty = with_generic_arg(p, str_ty.clone());
+        match self.replace(&vec_ty, &args.num, args.typ.as_ref(), padding) {
+            Ok(Some((ty, _))) => {
+                field.ty = ty.clone();
+                let strings = Fixed { member: member.clone(), kind: Kind::Str, ty: str_ty, num: inner, min: args.min, chars: None, inner: None };
+                let f = Fixed { member, kind: Kind::StrVec, ty, num: args.num, min: None, chars: None, inner: Some(Box::new(strings)) };
+                self.push_fixed(f);
+            }
+            Ok(None) => self.error(syn::Error::new_spanned(&field.ty, UNSUPPORTED)),
+            Err(e) => self.error(e),
+        }
+    }
+
+    /// With `gen_newtypes`, the newtype wrapping the string type `ty` of `member`, named after the
+    /// struct and the field, e.g. `FooProductCode` for `product_code` of `Foo`
+    fn newtype(&mut self, member: &Member, ty: Type, kind: Kind, num: &Size) -> Type {
+        let Some(prefix) = self.newtype_prefix.as_ref().filter(|_| kind == Kind::Str) else { return ty };
+        // the newtype is declared beside the struct, without its generics
+        if let Some(param) = mentioned(num.to_token_stream(), &self.const_params) {
+            let msg = format!("`gen_newtypes` can't wrap `{}`, whose size uses the struct's parameter `{}`, \
+                give it a size of its own or leave out `gen_newtypes`", member_name(member), param);
+            self.error(syn::Error::new_spanned(param, msg));
+            return ty;
+        }
+        let field: String = field_name(member).split('_').map(|w| {
+            let mut chars = w.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        }).collect::<Vec<String>>().concat();
+        let name = format_ident!("{}{}", prefix, field);
+        let chars = self.chars.then(|| (field_name(member), num.clone()));
+        self.newtypes.push((name.clone(), ty, chars));
+        parse_quote!(#name)
+    }
+
+    /// The `<field>_key` entry of `size_map` for `member`, fixing the key of a map
+    fn map_key_size(&self, member: &Member) -> Option<(Member, FieldArgs)> {
+        let Member::Named(ident) = member else { return None };
+        let key = Member::Named(format_ident!("{}_key", ident.unraw()));
+        let args = self.size_map.get(&key)?.clone();
+        Some((key, args))
+    }
+
+    /// Move the sizes given by serde name into `size_map`, keyed by the field with that
+    /// `#[serde(rename)]`, or failing that the field with that name
+    fn resolve_serde_names(&mut self, item: &ItemStruct) {
+        for (name, args) in std::mem::take(&mut self.serde_names) {
+            let found = item.fields.iter().find(|f| serde_renames(&f.attrs).is_some_and(|n| n.contains(&name.value())))
+                .or_else(|| item.fields.iter().find(|f| f.ident.as_ref().is_some_and(|i| i.unraw() == name.value())));
+            let Some(ident) = found.and_then(|f| f.ident.clone()) else {
+                let msg = format!("struct has no field renamed or named `{}`", name.value());
+                self.error(syn::Error::new_spanned(name, msg));
+                continue;
+            };
+            if let Err(e) = insert(&mut self.size_map, Member::Named(ident), args) {
+                self.error(syn::Error::new_spanned(name, e));
+            }
+        }
+    }
+
+    /// Key fields as they're declared in `item`, so that `name=4` finds `r#name` and `r#type=4`
+    /// finds `r#type` however either is written
+    fn raw_idents(&mut self, item: &ItemStruct) {
+        let declared = |key: Member| match key {
+            Member::Named(k) => {
+                let found = item.fields.iter().filter_map(|f| f.ident.as_ref()).find(|i| i.unraw() == k.unraw());
+                Member::Named(match found {
+                    Some(i) if i.to_string().starts_with("r#") => Ident::new_raw(&k.unraw().to_string(), k.span()),
+                    Some(_) => Ident::new(&k.unraw().to_string(), k.span()),
+                    None => k,
+                })
+            }
+            unnamed => unnamed,
+        };
+        self.size_map = std::mem::take(&mut self.size_map).into_iter().map(|(k, v)| (declared(k), v)).collect();
+        self.nested = std::mem::take(&mut self.nested).into_iter().map(|(k, v)| (declared(k), v)).collect();
+        self.skip = std::mem::take(&mut self.skip).into_iter().map(declared).collect();
+        self.tail = self.tail.take().map(declared);
+        for (_, fields) in self.excluded.values_mut() {
+            *fields = std::mem::take(fields).into_iter().map(declared).collect();
+        }
+    }
+
+    /// Take the sizes given by `#[fixed_field(N)]` or `#[fixed_field(N, ...)]` on the fields of
+    /// `item`, removing the attributes
+    fn field_attrs(&mut self, item: &mut ItemStruct) -> Result<()> {
+        let mut errors: Option<syn::Error> = None;
+        let members: Vec<Member> = item.fields.members().collect();
+        for (member, field) in members.into_iter().zip(item.fields.iter_mut()) {
+            let (attrs, rest) = std::mem::take(&mut field.attrs).into_iter().partition(|a| a.path().is_ident("fixed_field"));
+            field.attrs = rest;
+            for attr in attrs {
+                let result = attr.meta.require_list().and_then(|list| {
+                    let tokens = &list.tokens;
+                    let val = match syn::parse2(quote!((#tokens)))? {
+                        Expr::Paren(p) => *p.expr,
+                        val => val,
+                    };
+                    if self.skip.contains(&member) {
+                        let msg = format!("field `{}` is both given a size and skipped", member_name(&member));
+                        return Err(syn::Error::new_spanned(&attr, msg));
+                    }
+                    let args = field_args(&member_name(&member), &val)?;
+                    insert(&mut self.size_map, member.clone(), args).map_err(|e| syn::Error::new_spanned(&attr, e))
+                });
+                if let Err(e) = result {
+                    match &mut errors {
+                        Some(acc) => acc.combine(e),
+                        None => errors = Some(e),
+                    }
+                }
+            }
+        }
+        errors.map_or(Ok(()), Err)
+    }
+
+    /// Note the `#[cfg]` attributes of the fields of `item`
+    fn field_cfgs(&mut self, item: &ItemStruct) {
+        for (member, field) in item.fields.members().zip(&item.fields) {
+            let cfgs: Vec<_> = field.attrs.iter().filter(|a| a.path().is_ident("cfg")).cloned().collect();
+            if !cfgs.is_empty() {
+                self.cfgs.insert(member, cfgs);
+            }
+        }
+    }
+
+    /// Whether `m` was left out of the code generated for `feature` with `exclude_<feature>(...)`
+    fn excluded(&self, feature: &str, m: &Member) -> bool {
+        self.excluded.get(feature).is_some_and(|(_, fields)| fields.contains(m))
+    }
+
+    /// Error for each excluded field `item` doesn't have
+    fn unknown_excluded(&self, item: &ItemStruct) -> Option<syn::Error> {
+        let members: HashSet<Member> = item.fields.members().collect();
+        let mut missing: Vec<&Member> = self.excluded.values().flat_map(|(_, fields)| fields)
+            .filter(|m| !members.contains(*m))
+            .collect();
+        missing.sort_by_key(|m| member_name(m));
+        missing.into_iter()
+            .map(|m| syn::Error::new_spanned(m, format!("struct has no field named `{}`", member_name(m))))
+            .reduce(|mut acc, e| { acc.combine(e); acc })
+    }
+
+    /// The `#[cfg]` attributes of the field `m`, to put on code generated for it
+    fn cfg(&self, m: &Member) -> proc_macro2::TokenStream {
+        let attrs = self.cfgs.get(m).into_iter().flatten();
+        quote!(#(#attrs)*)
+    }
+
+    /// The length `len` of the field `m`, or `0` where a `#[cfg]` compiles the field out
+    fn cfg_len(&self, m: &Member, len: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let Some(attrs) = self.cfgs.get(m) else {
+            return len;
+        };
+        let preds = attrs.iter().filter_map(|a| a.meta.require_list().ok()).map(|l| &l.tokens);
+        quote!((if ::core::cfg!(all(#(#preds),*)) { #len } else { 0 }))
+    }
+
+    /// Check that some field is given a size, once `#[fixed_field]` has been read
+    fn check_sized(&self) -> Result<()> {
+        let sized = !self.size_map.is_empty() || !self.serde_names.is_empty();
+        if let (Some(typ), false) = (self.typ.as_ref().or(self.vec_typ.as_ref()), sized || self.wildcard.is_some()) {
+            return Err(syn::Error::new_spanned(typ, "`typ` is set but no field is given a size for it to apply to"));
+        }
+        if !sized && self.nested.is_empty() && self.wildcard.is_none() {
+            let msg = "#[fixed] requires at least one field=size argument or #[fixed_field] attribute";
+            return Err(syn::Error::new(Span::call_site(), msg));
+        }
+        Ok(())
+    }
+
+    /// Scale every size given in KiB with `unit=kib` to bytes
+    fn kib(&mut self) {
+        let sizes = self.size_map.values_mut().chain(self.serde_names.iter_mut().map(|(_, f)| f));
+        for args in sizes {
+            args.num = args.num.kib();
+            if let Some(inner) = &mut args.inner {
+                *inner = inner.kib();
+            }
+            if let Some(Size::Lit(min)) = args.min.as_ref().map(|min| Size::Lit(min.clone()).kib()) {
+                args.min = Some(min);
+            }
+        }
+        if let Some(num) = &mut self.wildcard {
+            *num = num.kib();
+        }
+    }
+
+    /// Resolve sizes naming one of the const generic parameters of `item`
+    fn const_params(&mut self, item: &ItemStruct) {
+        let params: HashSet<Ident> = item.generics.const_params().map(|p| p.ident.clone()).collect();
+        self.const_params = params.clone();
+        for args in self.size_map.values_mut() {
+            args.num.param(&params);
+            if let Some(inner) = &mut args.inner {
+                inner.param(&params);
+            }
+        }
+        if let Some(num) = &mut self.wildcard {
+            num.param(&params);
+        }
+    }
+
+    /// Error for each `size_map` key that didn't name a field of the struct
+    fn unmatched(&self) -> Option<syn::Error> {
+        let mut missing: Vec<&Member> = self.size_map.keys().chain(self.nested.keys()).chain(&self.skip)
+            .filter(|k| !self.seen.contains(*k))
+            .collect();
+        missing.sort_by_key(|k| member_name(k));
+        missing.into_iter()
+            .map(|k| match k {
+                Member::Named(i) => syn::Error::new_spanned(k, format!("struct has no field named `{}`", i)),
+                Member::Unnamed(i) => syn::Error::new_spanned(k, format!("struct has no field at index {}", i.index)),
+            })
+            .reduce(|mut acc, e| { acc.combine(e); acc })
+    }
+}
+
+impl Args {
+    /// Record a rewritten field, counting string sizes in chars with `unit=chars`
+    fn push_fixed(&mut self, mut f: Fixed) {
+        if self.chars && matches!(f.kind, Kind::Str | Kind::OptionStr | Kind::MapKey) {
+            let bytes = f.num.chars();
+            f.chars = Some(std::mem::replace(&mut f.num, bytes));
+        }
+        if let (true, Some(inner)) = (self.chars, &mut f.inner) {
+            let bytes = inner.num.chars();
+            inner.chars = Some(std::mem::replace(&mut inner.num, bytes));
+        }
+        self.fixed.push(f);
+    }
+
+    /// Rewrite `field` if `member` was given a size
+    fn fix(&mut self, member: Member, field: &mut Field) {
+        if let Some(key) = self.options.iter().find(|k| member == Member::Named((*k).clone())) {
+            if !self.size_map.contains_key(&member) && !self.nested.contains_key(&member) && !self.skip.contains(&member) {
+                let msg = format!("`{0}` is both an option and a field, give the field its size inside `fields({0} = N)`", key);
+                self.error(syn::Error::new_spanned(key, msg));
+            }
+        }
+        if self.skip.contains(&member) {
+            self.seen.insert(member);
+        } else if let Some((from, to)) = self.nested.get(&member) {
+            self.seen.insert(member.clone());
+            let ty = &field.ty;
+            if quote!(#from).to_string() == quote!(#ty).to_string() {
+                field.ty = to.clone();
+            } else {
+                let msg = format!("field `{}` isn't declared as `{}`", member_name(&member), quote!(#from));
+                self.error(syn::Error::new_spanned(ty, msg));
+            }
+        } else if let Some(args) = self.size_map.get(&member).filter(|a| a.inner.is_some()).cloned() {
+            self.seen.insert(member.clone());
+            self.fix_str_vec(member, field, args);
+        } else if let Some(args) = self.size_map.get(&member) {
+            self.seen.insert(member.clone());
+            let padding = self.padding(Some(args));
+            let replaced = match self.replace(&field.ty, &args.num, args.typ.as_ref(), padding) {
+                // with `force` anything we don't know is taken to be a `String`
+                Ok(None) if self.force && !is_fixed(&field.ty) => {
+                    self.replace(&parse_quote!(String), &args.num, args.typ.as_ref(), padding)
+                }
+                replaced => replaced,
+            };
+            match replaced {
+                Ok(Some((ty, kind))) => {
+                    let (num, min) = (args.num.clone(), args.min.clone());
+                    let ty = self.newtype(&member, ty, kind, &num);
+                    field.ty = ty.clone();
+                    self.push_fixed(Fixed { member, kind, ty, num, min, chars: None, inner: None });
+                }
+                // e.g. rewritten by another `#[fixed]` on the same struct
+                Ok(None) if is_fixed(&field.ty) && !self.strict => {}
+                Ok(None) if is_fixed(&field.ty) => {
+                    let msg = format!("field `{}` is already fixed length, remove it or `strict`", member_name(&member));
+                    self.error(syn::Error::new_spanned(&field.ty, msg));
+                }
+                Ok(None) if self.enum_as_int && matches!(field.ty, Type::Path(_)) => {
+                    let int = match &args.num {
+                        Size::Lit(n) => match n.base10_digits() {
+                            "1" => Some(quote!(u8)),
+                            "2" => Some(quote!(u16)),
+                            "4" => Some(quote!(u32)),
+                            "8" => Some(quote!(u64)),
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    let Some(int) = int else {
+                        let msg = "`enum_as_int` stores the field in an integer of 1, 2, 4 or 8 bytes";
+                        return self.error(syn::Error::new_spanned(&args.num, msg));
+                    };
+                    let int: Type = parse_quote!(#int);
+                    let ty = std::mem::replace(&mut field.ty, int.clone());
+                    self.enums.push((member, ty, int));
+                }
+                Ok(None) => self.error(syn::Error::new_spanned(&field.ty, UNSUPPORTED)),
+                Err(e) => self.error(e),
+            }
+        } else if let Some((key, args)) = self.map_key_size(&member) {
+            self.seen.insert(key.clone());
+            let name = member_name(&member);
+            let Some(key_ty) = map_key(&mut field.ty) else {
+                let msg = format!("`{}` fixes the key of `{}`, which isn't a `HashMap` or `BTreeMap`", member_name(&key), name);
+                return self.error(syn::Error::new_spanned(&field.ty, msg));
+            };
+            if !matches!(key_ty, Type::Path(p) if p.path.segments.last().is_some_and(|s| s.ident == "String" && s.arguments.is_none())) {
+                let msg = format!("the key of `{}` isn't a `String`", name);
+                return self.error(syn::Error::new_spanned(key_ty, msg));
+            }
+            let (num, chars) = if self.chars { (args.num.chars(), Some(&args.num)) } else { (args.num.clone(), None) };
+            let ty = self.str_type(&num, chars, args.typ.as_ref(), self.padding(Some(&args)));
+            *key_ty = ty.clone();
+            self.push_fixed(Fixed { member, kind: Kind::MapKey, ty, num: args.num, min: args.min, chars: None, inner: None });
+        } else if let Some(num) = &self.wildcard {
+            if let Ok(Some((ty, kind @ (Kind::Str | Kind::OptionStr | Kind::Char)))) = self.replace(&field.ty, num, None, self.padding(None)) {
+                let num = num.clone();
+                let ty = self.newtype(&member, ty, kind, &num);
+                field.ty = ty.clone();
+                self.push_fixed(Fixed { member, kind, ty, num, min: None, chars: None, inner: None });
+            }
+        }
+    }
+}
+
+impl Fold for Args {
+    fn fold_fields_named(&mut self, mut fields: FieldsNamed) -> FieldsNamed {
+        for field in fields.named.iter_mut() {
+            if let Some(ident) = field.ident.clone() {
+                let member = Member::Named(ident.clone());
+                let fixed = self.fixed.len();
+                let ty = field.ty.clone();
+                self.fix(member.clone(), field);
+                if self.no_rewrite {
+                    field.ty = ty;
+                }
+                if self.serde_with && self.fixed.len() > fixed {
+                    self.serde_with_attr(field);
+                }
+                if self.require_rename && (self.fixed.len() > fixed || self.nested.contains_key(&member))
+                    && serde_renames(&field.attrs).is_none() {
+                    let msg = format!("field `{}` needs a `#[serde(rename = \"...\")]` with `require_rename`", ident);
+                    self.error(syn::Error::new_spanned(&ident, msg));
+                }
+            }
+        }
+        fields
+    }
+
+    fn fold_fields_unnamed(&mut self, mut fields: FieldsUnnamed) -> FieldsUnnamed {
+        for (i, field) in fields.unnamed.iter_mut().enumerate() {
+            let (fixed, ty) = (self.fixed.len(), field.ty.clone());
+            self.fix(Member::Unnamed(i.into()), field);
+            if self.no_rewrite {
+                field.ty = ty;
+            }
+            if self.serde_with && self.fixed.len() > fixed {
+                self.serde_with_attr(field);
+            }
+        }
+        fields
+    }
+}
+
+/// The names given by `#[serde(rename = "...")]` or `#[serde(rename(serialize = "...", ...))]`,
+/// or `None` if `attrs` don't rename the field
+fn serde_renames(attrs: &[syn::Attribute]) -> Option<Vec<String>> {
+    let mut names = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        // other serde options are left for serde to check
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let names = names.get_or_insert_with(Vec::new);
+                if meta.input.peek(Token![=]) {
+                    names.push(meta.value()?.parse::<LitStr>()?.value());
+                } else {
+                    meta.parse_nested_meta(|m| {
+                        names.push(m.value()?.parse::<LitStr>()?.value());
+                        Ok(())
+                    })?;
+                }
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            } else if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                meta.input.parse::<proc_macro2::Group>()?;
+            }
+            Ok(())
+        });
+    }
+    names
+}
+
+/// `from_bytes_ref` for `item`, erroring unless it's only `#[repr(C)]` and every field of `layout`
+/// is a byte array, which is what makes borrowing any `FIXED_LEN` bytes as it sound
+fn bytes_ref_method(item: &ItemStruct, layout: &[(Member, Layout)]) -> Result<proc_macro2::TokenStream> {
+    let mut repr_c = false;
+    for attr in item.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("C") {
+                // `align` or `packed` changes the size or alignment the cast relies on
+                let msg = "`gen_bytes_ref` needs the struct to be only `#[repr(C)]`, without other hints";
+                return Err(syn::Error::new_spanned(&meta.path, msg));
+            }
+            repr_c = true;
+            Ok(())
+        })?;
+    }
+    if !repr_c {
+        let msg = "`gen_bytes_ref` needs the struct to be `#[repr(C)]`, add it or `repr=C`";
+        return Err(syn::Error::new_spanned(&item.ident, msg));
+    }
+    if let Some((member, _)) = layout.iter().find(|(_, l)| !matches!(l, Layout::Bytes(_))) {
+        let msg = format!("`gen_bytes_ref` needs every field to be a byte array, `{}` isn't", member_name(member));
+        return Err(syn::Error::new_spanned(member, msg));
+    }
+    let vis = &item.vis;
+    let msg = format!("`{}` isn't laid out as `FIXED_LEN` bytes with an alignment of 1", item.ident);
+    Ok(quote! {
+        /// Borrow `b` as the struct without copying it, each field viewing its bytes in turn
+        #vis fn from_bytes_ref(b: &[u8; Self::FIXED_LEN]) -> &Self {
+            const {
+                ::core::assert!(
+                    ::core::mem::size_of::<Self>() == Self::FIXED_LEN && ::core::mem::align_of::<Self>() == 1,
+                    #msg,
+                )
+            };
+            // SAFETY: the struct is `#[repr(C)]` with only byte array fields, so it has no
+            // padding, an alignment of 1, a size of `FIXED_LEN` and no invalid bit patterns
+            unsafe { &*(b as *const [u8; Self::FIXED_LEN]).cast::<Self>() }
+        }
+    })
+}
+
+/// Wrapper for `ArrayString` which serializes padded to its capacity with `PAD`, or to `CHARS`
+/// chars when that's given, and trims it again when deserializing
+fn padded_type(name: &Ident, vis: &Visibility, arrayvec: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let doc = "`ArrayString` which serializes padded to its capacity with `PAD`, generated by `#[fixed]`";
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        #[repr(transparent)]
+        #vis struct #name<const CAP: usize, const PAD: u8, const RIGHT: bool = false, const KEEP: bool = false, const CHARS: usize = 0>(pub #arrayvec::ArrayString<CAP>);
+
+        impl<const CAP: usize, const PAD: u8, const RIGHT: bool, const KEEP: bool, const CHARS: usize> ::core::ops::Deref for #name<CAP, PAD, RIGHT, KEEP, CHARS> {
+            type Target = #arrayvec::ArrayString<CAP>;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<const CAP: usize, const PAD: u8, const RIGHT: bool, const KEEP: bool, const CHARS: usize> ::core::ops::DerefMut for #name<CAP, PAD, RIGHT, KEEP, CHARS> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl<const CAP: usize, const PAD: u8, const RIGHT: bool, const KEEP: bool, const CHARS: usize> ::core::convert::From<#arrayvec::ArrayString<CAP>> for #name<CAP, PAD, RIGHT, KEEP, CHARS> {
+            fn from(s: #arrayvec::ArrayString<CAP>) -> Self {
+                Self(s)
+            }
+        }
+
+        impl<'a, const CAP: usize, const PAD: u8, const RIGHT: bool, const KEEP: bool, const CHARS: usize> ::core::convert::TryFrom<&'a str> for #name<CAP, PAD, RIGHT, KEEP, CHARS> {
+            type Error = #arrayvec::CapacityError<&'a str>;
+            fn try_from(s: &'a str) -> ::core::result::Result<Self, Self::Error> {
+                #arrayvec::ArrayString::from(s).map(Self)
+            }
+        }
+
+        impl<const CAP: usize, const PAD: u8, const RIGHT: bool, const KEEP: bool, const CHARS: usize> ::serde::Serialize for #name<CAP, PAD, RIGHT, KEEP, CHARS> {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> {
+                let mut padded = #arrayvec::ArrayString::<CAP>::new();
+                if !RIGHT {
+                    padded.push_str(&self.0);
+                }
+                // with `unit=chars` padded to the size in chars, which always fits in `CAP` bytes
+                let (len, width) = match CHARS {
+                    0 => (self.0.len(), CAP),
+                    chars => (self.0.chars().count(), chars),
+                };
+                for _ in len..width {
+                    padded.push(PAD as char);
+                }
+                if RIGHT {
+                    padded.push_str(&self.0);
+                }
+                serializer.serialize_str(&padded)
+            }
+        }
+
+        impl<'de, const CAP: usize, const PAD: u8, const RIGHT: bool, const KEEP: bool, const CHARS: usize> ::serde::Deserialize<'de> for #name<CAP, PAD, RIGHT, KEEP, CHARS> {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::core::result::Result<Self, D::Error> {
+                struct Visitor<const CAP: usize, const PAD: u8, const RIGHT: bool, const KEEP: bool, const CHARS: usize>;
+                impl<'de, const CAP: usize, const PAD: u8, const RIGHT: bool, const KEEP: bool, const CHARS: usize> ::serde::de::Visitor<'de> for Visitor<CAP, PAD, RIGHT, KEEP, CHARS> {
+                    type Value = #name<CAP, PAD, RIGHT, KEEP, CHARS>;
+                    fn expecting(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        ::core::write!(f, "a string of at most {} bytes", CAP)
+                    }
+                    fn visit_str<E: ::serde::de::Error>(self, v: &str) -> ::core::result::Result<Self::Value, E> {
+                        let trimmed = match (KEEP, RIGHT) {
+                            (true, _) => v,
+                            (false, true) => v.trim_start_matches(PAD as char),
+                            (false, false) => v.trim_end_matches(PAD as char),
+                        };
+                        #arrayvec::ArrayString::from(trimmed)
+                            .map(#name)
+                            .map_err(|_| E::invalid_length(trimmed.len(), &self))
+                    }
+                }
+                deserializer.deserialize_str(Visitor::<CAP, PAD, RIGHT, KEEP, CHARS>)
+            }
+        }
+    }
+}
+
+/// `#[repr(transparent)]` newtype `name` around the fixed string type `ty`, with `TryFrom<&str>`
+/// when the generated conversions need it, and serializing as `ty` when `serde` is in use
+/// 
+/// `try_from` is the struct's error type, with the field and its size in chars for `unit=chars`,
+/// whose capacity `ty` can't enforce as it's sized in bytes.
+fn newtype(name: &Ident, ty: &Type, vis: &Visibility, try_from: Option<(&Ident, Option<&CharsOf>)>, serde: bool) -> proc_macro2::TokenStream {
+    let doc = format!("`{}` of its own for one field, generated by `#[fixed(gen_newtypes)]`", quote!(#ty).to_string().replace(' ', ""));
+    let try_from = try_from.map(|(error_ty, chars)| match chars {
+        Some((field, chars)) => quote! {
+            impl<'a> ::core::convert::TryFrom<&'a str> for #name {
+                type Error = #error_ty;
+                fn try_from(s: &'a str) -> ::core::result::Result<Self, Self::Error> {
+                    let got = s.chars().count();
+                    let overflow = #error_ty::Overflow { field: #field, cap: #chars, got };
+                    if got > #chars {
+                        return ::core::result::Result::Err(overflow);
+                    }
+                    <#ty as ::core::convert::TryFrom<&'a str>>::try_from(s).map(Self).map_err(|_| overflow)
+                }
+            }
+        },
+        None => quote! {
+            impl<'a> ::core::convert::TryFrom<&'a str> for #name {
+                type Error = <#ty as ::core::convert::TryFrom<&'a str>>::Error;
+                fn try_from(s: &'a str) -> ::core::result::Result<Self, Self::Error> {
+                    <#ty as ::core::convert::TryFrom<&'a str>>::try_from(s).map(Self)
+                }
+            }
+        },
+    });
+    let serde = serde.then(|| quote! {
+        impl ::serde::Serialize for #name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> {
+                ::serde::Serialize::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for #name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::core::result::Result<Self, D::Error> {
+                <#ty as ::serde::Deserialize<'de>>::deserialize(deserializer).map(Self)
+            }
+        }
+    });
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        #[repr(transparent)]
+        #vis struct #name(pub #ty);
+
+        impl ::core::ops::Deref for #name {
+            type Target = #ty;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl ::core::ops::DerefMut for #name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl ::core::convert::From<#ty> for #name {
+            fn from(s: #ty) -> Self {
+                Self(s)
+            }
+        }
+
+        #try_from
+        #serde
+    }
+}
+
+/// Wrapper for `ArrayVec<u8, CAP>` which serializes a `u32` length followed by its bytes, padded
+/// with zeros to its capacity
+fn prefixed_type(name: &Ident, vis: &Visibility, arrayvec: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let doc = "`ArrayVec<u8, CAP>` which serializes its length followed by its bytes padded to `CAP`, generated by `#[fixed]`";
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        #[repr(transparent)]
+        #vis struct #name<const CAP: usize>(pub #arrayvec::ArrayVec<u8, CAP>);
+
+        impl<const CAP: usize> ::core::ops::Deref for #name<CAP> {
+            type Target = #arrayvec::ArrayVec<u8, CAP>;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<const CAP: usize> ::core::ops::DerefMut for #name<CAP> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl<const CAP: usize> ::core::convert::From<#arrayvec::ArrayVec<u8, CAP>> for #name<CAP> {
+            fn from(v: #arrayvec::ArrayVec<u8, CAP>) -> Self {
+                Self(v)
+            }
+        }
+
+        impl<const CAP: usize> ::core::convert::TryFrom<&[u8]> for #name<CAP> {
+            type Error = #arrayvec::CapacityError;
+            fn try_from(v: &[u8]) -> ::core::result::Result<Self, Self::Error> {
+                #arrayvec::ArrayVec::try_from(v).map(Self)
+            }
+        }
+
+        impl<const CAP: usize> ::serde::Serialize for #name<CAP> {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> {
+                use ::serde::ser::SerializeTuple;
+                let mut tuple = serializer.serialize_tuple(CAP + 1)?;
+                tuple.serialize_element(&(self.0.len() as u32))?;
+                for b in self.0.iter().chain(::core::iter::repeat(&0)).take(CAP) {
+                    tuple.serialize_element(b)?;
+                }
+                tuple.end()
+            }
+        }
+
+        impl<'de, const CAP: usize> ::serde::Deserialize<'de> for #name<CAP> {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::core::result::Result<Self, D::Error> {
+                struct Visitor<const CAP: usize>;
+                impl<'de, const CAP: usize> ::serde::de::Visitor<'de> for Visitor<CAP> {
+                    type Value = #name<CAP>;
+                    fn expecting(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        ::core::write!(f, "a length of at most {} followed by {} bytes", CAP, CAP)
+                    }
+                    fn visit_seq<A: ::serde::de::SeqAccess<'de>>(self, mut seq: A) -> ::core::result::Result<Self::Value, A::Error> {
+                        use ::serde::de::Error;
+                        let len: u32 = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(0, &self))?;
+                        let len = len as usize;
+                        if len > CAP {
+                            return ::core::result::Result::Err(A::Error::invalid_length(len, &self));
+                        }
+                        let mut v = #arrayvec::ArrayVec::new();
+                        for i in 0..CAP {
+                            let b: u8 = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(i + 1, &self))?;
+                            if i < len {
+                                v.push(b);
+                            }
+                        }
+                        ::core::result::Result::Ok(#name(v))
+                    }
+                }
+                deserializer.deserialize_tuple(CAP + 1, Visitor::<CAP>)
+            }
+        }
+    }
+}
+
+/// Error returned by the generated conversions, setters and `validate`
+fn error_type(name: &Ident, vis: &Visibility) -> proc_macro2::TokenStream {
+    let doc = "Error returned by the conversions, setters and `validate` generated by `#[fixed]`";
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        #vis enum #name {
+            /// a field is shorter than its `min`
+            TooShort { field: &'static str, min: usize, len: usize },
+            /// a field is longer than its capacity
+            Overflow { field: &'static str, cap: usize, got: usize },
+            /// a nested field failed to convert
+            Nested { field: &'static str },
+            /// a string, or a byte array with `validate_utf8`, isn't valid UTF-8
+            Utf8 { field: &'static str },
+            /// an `enum_as_int` field holds a value its enum doesn't convert from
+            Discriminant { field: &'static str, value: u64 },
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                match self {
+                    Self::TooShort { field, min, len } =>
+                        ::core::write!(f, "field `{}` has length {}, expected at least {}", field, len, min),
+                    Self::Overflow { field, cap, got } =>
+                        ::core::write!(f, "field `{}` has length {}, expected at most {}", field, got, cap),
+                    Self::Nested { field } => ::core::write!(f, "field `{}` couldn't be converted", field),
+                    Self::Utf8 { field } => ::core::write!(f, "field `{}` isn't valid UTF-8", field),
+                    Self::Discriminant { field, value } =>
+                        ::core::write!(f, "field `{}` holds {}, which isn't a discriminant of its enum", field, value),
+                }
+            }
+        }
+
+        impl ::core::error::Error for #name {}
+    }
+}
+
+/// Visibility inside the helper module that gives an item `vis` once re-exported from the parent
+fn helper_vis(vis: &Visibility) -> Visibility {
+    match vis {
+        Visibility::Inherited => parse_quote!(pub(super)),
+        Visibility::Restricted(r) if r.path.is_ident("self") => parse_quote!(pub(super)),
+        Visibility::Restricted(r) if r.path.segments.first().is_some_and(|s| s.ident == "self") => {
+            let rest = r.path.segments.iter().skip(1);
+            parse_quote!(pub(in super #(:: #rest)*))
+        }
+        Visibility::Restricted(r) if r.path.segments.first().is_some_and(|s| s.ident == "super") => {
+            let path = &r.path;
+            parse_quote!(pub(in super::#path))
+        }
+        _ => vis.clone(),
+    }
+}
+
+/// Wrap the helper items generated for `name` in a hidden `__fixed_<name>` module, re-exporting
+/// `names` from it with `vis`, so they can't collide with those of other structs
+/// 
+/// Only items spelled with absolute paths can go in, as the module can't see the items of a
+/// function body the struct is declared in.
+fn helper_module(name: &Ident, vis: &Visibility, items: proc_macro2::TokenStream, names: &[&Ident]) -> proc_macro2::TokenStream {
+    if items.is_empty() {
+        return items;
+    }
+    let module = helper_module_name(name);
+    let reexport = (!names.is_empty()).then(|| quote!(#vis use #module::{#(#names),*};));
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+            #items
+        }
+        #reexport
+    }
+}
+
+/// Whether `attr` is another `#[fixed]`, stacked after the one being expanded
+fn is_fixed_attr(attr: &syn::Attribute) -> bool {
+    attr.path().segments.last().is_some_and(|s| s.ident == "fixed")
+}
+
+/// Hand the helper `items` and the `names` to re-export on to the stacked `#[fixed]` `attr`, to
+/// emit in its helper module
+fn forward_helpers(attr: &mut syn::Attribute, names: &[&Ident], items: TokenStream) {
+    if items.is_empty() {
+        return;
+    }
+    let helpers = quote!(__helpers(#(#names),*) { #items });
+    match &mut attr.meta {
+        syn::Meta::List(list) => {
+            let comma = list.tokens.clone().into_iter().last()
+                .is_none_or(|t| matches!(t, proc_macro2::TokenTree::Punct(p) if p.as_char() == ','));
+            if !comma {
+                list.tokens.extend(quote!(,));
+            }
+            list.tokens.extend(helpers);
+        }
+        syn::Meta::Path(path) => attr.meta = parse_quote!(#path(#helpers)),
+        // not a valid `#[fixed]`, which will say so itself
+        syn::Meta::NameValue(_) => {}
+    }
+}
+
+/// Name of the hidden module holding the helpers for the struct `name`, e.g. `__fixed_foo_bar`
+/// for `FooBar`
+fn helper_module_name(name: &Ident) -> Ident {
+    let mut snake = String::from("__fixed");
+    for c in name.to_string().trim_start_matches("r#").chars() {
+        if c.is_uppercase() || snake.ends_with("__fixed") {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    Ident::new(&snake, name.span())
+}
+
+/// Error for the macro being applied to something other than a struct
+fn not_a_struct(span: impl ToTokens, found: &str) -> TokenStream {
+    let msg = format!("#[fixed] can only be applied to structs, found {}", found);
+    syn::Error::new_spanned(span, msg).to_compile_error()
+}
+
+/// Expand `#[fixed(args)]` on `input`, see the `fixed-size` crate for the arguments
+pub fn fixed(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut args = match syn::parse2::<Args>(args) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error(),
+    };
+    let mut input = match syn::parse2::<Item>(input) {
+        Ok(Item::Struct(input)) => input,
+        Ok(Item::Enum(e)) => return not_a_struct(e.enum_token, "enum"),
+        Ok(Item::Union(u)) => return not_a_struct(u.union_token, "union"),
+        Ok(Item::Fn(f)) => return not_a_struct(f.sig.fn_token, "function"),
+        Ok(other) => return not_a_struct(other, "another item"),
+        Err(e) => return e.to_compile_error(),
+    };
+    if let Err(e) = args.field_attrs(&mut input).and_then(|_| args.check_sized()) {
+        return e.to_compile_error();
+    }
+    args.field_cfgs(&input);
+    if args.kib {
+        args.kib();
+    }
+    let original = args.suffix.as_ref().map(|suffix| {
+        let original = input.clone();
+        input.ident = format_ident!("{}{}", input.ident, suffix);
+        original
+    });
+    if args.serde_pad {
+        args.padded = Some(format_ident!("{}Padded", input.ident));
+    }
+    if args.serde_len {
+        args.prefixed = Some(format_ident!("{}Prefixed", input.ident));
+    }
+    if args.serde_with {
+        args.with_module = Some(helper_module_name(&input.ident));
+    }
+    if let Some(hints) = &args.repr {
+        if let Some(attr) = input.attrs.iter().find(|a| a.path().is_ident("repr")) {
+            args.error(syn::Error::new_spanned(attr, "struct already has a `#[repr]`, remove it or the `repr` option"));
+        } else {
+            input.attrs.push(parse_quote!(#[repr(#(#hints),*)]));
+        }
+    }
+    args.resolve_serde_names(&input);
+    if args.gen_newtypes {
+        args.newtype_prefix = Some(input.ident.clone());
+    }
+    args.const_params(&input);
+    args.raw_idents(&input);
+    let vis = input.vis.clone();
+    let inner_vis = helper_vis(&vis);
+    let mut output = args.fold_item_struct(input);
+    if !args.no_rewrite {
+        drop_unused_lifetimes(&mut output);
+    }
+    let wrapped = |typ: &Ident| args.newtypes.iter()
+        .any(|(_, ty, _)| matches!(ty, Type::Path(p) if p.path.segments.first().is_some_and(|s| s.ident == *typ)));
+    let padded = args.padded.as_ref().filter(|p| args.fixed.iter().any(|f| f.ty_is(Some(p))) || wrapped(p))
+        .map(|name| padded_type(name, &inner_vis, &args.arrayvec()));
+    let prefixed = args.prefixed.as_ref().filter(|_| args.fixed.iter().any(|f| f.ty_is(args.prefixed.as_ref())))
+        .map(|name| prefixed_type(name, &inner_vis, &args.arrayvec()));
+    let try_from = original.is_some() || args.gen_try_from || args.gen_bytes;
+    let error_ty = format_ident!("{}FixedError", original.as_ref().unwrap_or(&output).ident);
+    let newtypes = args.newtypes.iter().map(|(name, ty, chars)| {
+        let try_from = try_from.then_some((&error_ty, chars.as_ref()));
+        newtype(name, ty, &vis, try_from, args.serde_pad || args.serde_len)
+    });
+    let newtypes = quote!(#(#newtypes)*);
+    let conversions = original.as_ref().map(|o| args.conversions(o, &output, &error_ty));
+    if let Some(e) = args.unmatched().into_iter().chain(args.unknown_excluded(&output)).reduce(|mut acc, e| { acc.combine(e); acc }) {
+        args.error(e);
+    }
+    if let Some(tail) = args.tail.clone().filter(|t| args.seen.contains(t) && output.fields.members().last().as_ref() != Some(t)) {
+        let msg = format!("the tail `{}` must be the last field, after the fixed header", member_name(&tail));
+        args.error(syn::Error::new_spanned(tail, msg));
+    }
+    let setters = (args.gen_try_from && args.on_overflow == Overflow::Error || args.gen_mutators)
+        && args.fixed.iter().any(|f| f.kind == Kind::Str)
+        || args.fixed.iter().any(|f| f.kind == Kind::Str && args.zero_pad(&f.member));
+    let error_type = (original.is_some() || setters || args.gen_bytes || !args.enums.is_empty() || args.validate_utf8 || args.no_rewrite || args.fixed.iter().any(|f| f.inner.as_deref().unwrap_or(f).min.is_some()))
+        .then(|| error_type(&error_ty, &inner_vis));
+    // stacked attributes may each need the same helper, keep the one handed on
+    let (forwarded_names, forwarded) = std::mem::take(&mut args.forwarded);
+    let fresh = |name: &Ident| !forwarded_names.contains(name);
+    let padded = padded.filter(|_| args.padded.as_ref().is_some_and(fresh));
+    let prefixed = prefixed.filter(|_| args.prefixed.as_ref().is_some_and(fresh));
+    let error_type = error_type.filter(|_| fresh(&error_ty));
+    let mut helpers: Vec<&Ident> = forwarded_names.iter().collect();
+    helpers.extend(padded.is_some().then_some(args.padded.as_ref()).flatten());
+    helpers.extend(prefixed.is_some().then_some(args.prefixed.as_ref()).flatten());
+    helpers.extend(error_type.is_some().then_some(&error_ty));
+    let serde_with = args.serde_with.then(|| args.serde_with_fns(&inner_vis));
+    let items = quote!(#forwarded #padded #prefixed #error_type #serde_with);
+    // the last `#[fixed]` on the struct emits the helpers of them all, so they're defined once
+    let helpers = match output.attrs.iter_mut().find(|a| is_fixed_attr(a)) {
+        Some(next) => {
+            forward_helpers(next, &helpers, items);
+            None
+        }
+        None => Some(helper_module(&output.ident, &vis, items, &helpers)),
+    };
+    let methods = args.methods(&output, &error_ty);
+    let default = args.gen_default.then(|| args.default_impl(&output));
+    let display = args.gen_display.then(|| args.display_impl(&output));
+    let debug = args.gen_debug.then(|| args.debug_impl(&output));
+    let asserts = args.gen_static_asserts.then(|| args.static_asserts(&output));
+    let schema_file = args.schema_file.as_ref().map(|path| quote!(const _: &[u8] = ::core::include_bytes!(#path);));
+    let errors = args.errors.map(|e| e.to_compile_error());
+    quote!(#original #output #helpers #newtypes #conversions #methods #default #display #debug #asserts #schema_file #errors)
+}
+
+/// Rewrite `field` to its fixed length equivalent of capacity `size`, as `#[fixed(field=size)]`
+/// would
+/// 
+/// `String`, `Box<str>` and `Cow<str>` become `::arrayvec::ArrayString<size>`, and
+/// `Option<String>` an `Option` of one. `Vec<u8>` and `Bytes` become `[u8; size]` and any other
+/// `Vec<T>` an `::arrayvec::ArrayVec<T, size>`. A field which is already fixed length is returned
+/// unchanged, and any other type is an error spanned at it.
+/// ```rust
+/// use syn::{parse_quote, Field};
+/// 
+/// let field: Field = parse_quote!(pub name: String);
+/// let fixed = fixed_size_core::transform_field(&field, 8).unwrap();
+/// assert_eq!(fixed, parse_quote!(pub name: ::arrayvec::ArrayString<8>));
+/// ```
+pub fn transform_field(field: &Field, size: usize) -> Result<Field> {
+    let num = check_size(&LitInt::new(&size.to_string(), Span::call_site()))?;
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(0)),
+    };
+    let mut args = Args::default();
+    args.size_map.insert(member.clone(), FieldArgs::new(Size::Lit(num)));
+    let mut field = field.clone();
+    args.fix(member, &mut field);
+    match args.errors {
+        Some(e) => Err(e),
+        None => Ok(field),
+    }
+}
+
+/// Arguments to `fixed_ty!`, a size optionally followed by `typ=`, `typ_shape=` and `crate=`
+struct TyArgs {
+    num: Size,
+    args: Args,
+}
+
+impl Parse for TyArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let num = size(&input.parse()?)?;
+        let mut args = Args::default();
+        if input.parse::<Option<Token![,]>>()?.is_none() {
+            return Ok(TyArgs { num, args });
+        }
+        for arg in Punctuated::<Arg, Token![,]>::parse_terminated(input)? {
+            match arg {
+                Arg::Crate(token, path) => {
+                    if args.krate.is_some() {
+                        return Err(syn::Error::new_spanned(token, "`crate` specified more than once"));
+                    }
+                    args.krate = Some(path);
+                }
+                Arg::Expr(Expr::Assign(a)) if matches!(&*a.left, Expr::Path(p) if p.path.is_ident("typ")) => {
+                    let (Expr::Path(key), Expr::Path(v)) = (&*a.left, &*a.right) else {
+                        return Err(syn::Error::new_spanned(&a.right, "typ must be a type path, e.g. typ=MyString"));
+                    };
+                    set_once(&mut args.typ, key_ident(key)?, v.path.clone())?;
+                }
+                Arg::Expr(Expr::Assign(a)) if matches!(&*a.left, Expr::Path(p) if p.path.is_ident("typ_shape")) => {
+                    let span = &a.left;
+                    if args.typ_shape.replace(shape(&a.right)?).is_some() {
+                        return Err(syn::Error::new_spanned(span, "`typ_shape` specified more than once"));
+                    }
+                }
+                _ => {
+                    let msg = "expected only `typ=Type`, `typ_shape=\"<...>\"` or `crate=path` after the size";
+                    return Err(syn::Error::new(Span::call_site(), msg));
+                }
+            }
+        }
+        if let (Some(shape), None) = (&args.typ_shape, &args.typ) {
+            return Err(syn::Error::new_spanned(shape, "typ_shape needs a `typ` to apply to"));
+        }
+        Ok(TyArgs { num, args })
+    }
+}
+
+/// Expand `fixed_ty!(input)`, see the `fixed-size` crate for the arguments
+pub fn fixed_ty(input: TokenStream) -> TokenStream {
+    let TyArgs { num, args } = match syn::parse2::<TyArgs>(input) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error(),
+    };
+    let ty = args.str_type(&num, None, None, args.padding(None));
+    quote!(#ty)
+}
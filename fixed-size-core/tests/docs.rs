@@ -0,0 +1,65 @@
+use fixed_size_core::fixed;
+use quote::quote;
+use syn::{Attribute, Item, ItemStruct};
+
+/// The struct named `name` in the expansion of `#[fixed(args)]` on `input`
+fn expand(args: proc_macro2::TokenStream, input: proc_macro2::TokenStream, name: &str) -> ItemStruct {
+    let file: syn::File = syn::parse2(fixed(args, input)).unwrap();
+    file.items.into_iter()
+        .find_map(|item| match item {
+            Item::Struct(s) if s.ident == name => Some(s),
+            _ => None,
+        })
+        .unwrap()
+}
+
+fn docs(attrs: &[Attribute]) -> Vec<&Attribute> {
+    attrs.iter().filter(|a| a.path().is_ident("doc")).collect()
+}
+
+#[test]
+fn docs_are_kept_in_order() {
+    let input = quote! {
+        /// A record
+        ///
+        /// with a second paragraph.
+        #[derive(Debug)]
+        #[doc = "and an attribute after the derive."]
+        pub struct Record {
+            /// Replaced with a fixed string
+            #[allow(dead_code)]
+            /// and documented either side of another attribute.
+            pub name: String,
+            /// Left alone
+            pub count: u32,
+        }
+    };
+    let original: ItemStruct = syn::parse2(input.clone()).unwrap();
+    let expanded = expand(quote!(name=4), input, "Record");
+    assert_eq!(docs(&expanded.attrs), docs(&original.attrs));
+    for (field, original) in expanded.fields.iter().zip(&original.fields) {
+        assert_eq!(docs(&field.attrs), docs(&original.attrs));
+    }
+    assert_eq!(docs(&expanded.fields.iter().next().unwrap().attrs).len(), 2);
+}
+
+#[test]
+fn docs_are_kept_on_tuple_fields_and_siblings() {
+    let input = quote! {
+        /// A pair
+        pub struct Pair(
+            /// Replaced in a tuple struct
+            pub String,
+            /// Left alone too
+            pub u8,
+        );
+    };
+    let original: ItemStruct = syn::parse2(input.clone()).unwrap();
+    for (args, name) in [(quote!(0=8), "Pair"), (quote!(0=8, suffix=Fixed), "PairFixed")] {
+        let expanded = expand(args, input.clone(), name);
+        assert_eq!(docs(&expanded.attrs), docs(&original.attrs));
+        for (field, original) in expanded.fields.iter().zip(&original.fields) {
+            assert_eq!(docs(&field.attrs), docs(&original.attrs));
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use fixed_size_core::fixed;
+use quote::quote;
+use syn::{Item, ItemStruct, Member};
+
+#[test]
+fn attributes_and_field_order_are_kept() {
+    let input = quote! {
+        #[derive(Clone, PartialEq, Debug, serde::Serialize)]
+        #[serde(rename_all = "UPPERCASE")]
+        #[allow(dead_code)]
+        pub(crate) struct Record {
+            #[serde(rename = "ID")]
+            id: u32,
+            #[serde(default)]
+            #[allow(unused)]
+            name: String,
+            tags: Vec<String>,
+            #[cfg_attr(test, serde(skip))]
+            score: f32,
+            notes: String,
+        }
+    };
+    let original: ItemStruct = syn::parse2(input.clone()).unwrap();
+    let file: syn::File = syn::parse2(fixed(quote!(name=4, notes=8), input)).unwrap();
+    let structs: Vec<ItemStruct> = file.items.into_iter()
+        .filter_map(|item| match item {
+            Item::Struct(s) => Some(s),
+            _ => None,
+        })
+        .collect();
+    let [expanded] = &structs[..] else { panic!("expected only the struct, got {}", structs.len()) };
+    assert_eq!(expanded.attrs, original.attrs);
+    assert_eq!((&expanded.vis, &expanded.ident, &expanded.generics), (&original.vis, &original.ident, &original.generics));
+    let members = |s: &ItemStruct| s.fields.members().collect::<Vec<Member>>();
+    assert_eq!(members(expanded), members(&original));
+    for (field, original) in expanded.fields.iter().zip(&original.fields) {
+        assert_eq!(field.attrs, original.attrs);
+        let fixed = ["name", "notes"].iter().any(|n| original.ident.as_ref().is_some_and(|i| i == n));
+        assert_eq!(field.ty == original.ty, !fixed);
+    }
+}
@@ -0,0 +1,28 @@
+use fixed_size_core::transform_field;
+use syn::{parse_quote, Field};
+
+#[test]
+fn rewrites_named_and_unnamed_fields() {
+    let field: Field = parse_quote!(#[serde(default)] pub name: Option<String>);
+    let expected: Field = parse_quote!(#[serde(default)] pub name: Option<::arrayvec::ArrayString<4>>);
+    assert_eq!(transform_field(&field, 4).unwrap(), expected);
+    let field: Field = parse_quote!(Vec<u8>);
+    assert_eq!(transform_field(&field, 16).unwrap(), parse_quote!([u8; 16]));
+    let field: Field = parse_quote!(points: Vec<u16>);
+    assert_eq!(transform_field(&field, 2).unwrap(), parse_quote!(points: ::arrayvec::ArrayVec<u16, 2>));
+}
+
+#[test]
+fn leaves_fixed_fields_alone() {
+    let field: Field = parse_quote!(name: ::arrayvec::ArrayString<4>);
+    assert_eq!(transform_field(&field, 4).unwrap(), field);
+}
+
+#[test]
+fn reports_unsupported_types() {
+    let field: Field = parse_quote!(n: u32);
+    let err = transform_field(&field, 4).unwrap_err();
+    assert!(err.to_string().starts_with("don't know how to make this type fixed length"));
+    let field: Field = parse_quote!(name: String);
+    assert_eq!(transform_field(&field, 0).unwrap_err().to_string(), "size must be greater than zero");
+}
@@ -13,9 +13,11 @@
 //! ````
 //! Prost will create use [`String`] for the my_string field. If you have a binary format requiring
 //! exactly 4 characters in a string this will be difficult to handle in a generic manner. If you add
-//! the `#[fixed(my_string=4)]` attribute then you'll end up with a `ArrayString::<4>` instead.
+//! the `#[fixed(my_string=4)]` attribute then you'll end up with a `ArrayString<4>` instead.
 //! 
 //! By default, ArrayString will be used but this can be overridden with `#[fixed(typ=MyString, thestring=4)]`
+//! or for a single field with `#[fixed(thestring=(4, MyString))]`. A per-field type takes precedence
+//! over `typ`. Either may be a path such as `typ=crate::types::MyString`.
 //! The typical use is
 //! ```rust
 //! use arrayvec::ArrayString;
@@ -37,113 +39,1204 @@
 //! }
 //! // More impls, probably AsMut, etc.
 //! ```
+//! Other fixed capacity containers work the same way, e.g. `typ=heapless::String` for `no_std`
+//! targets. The attribute only runs at compile time and the rewritten struct refers to nothing
+//! but the container types, so the crate has no runtime dependency of its own. The optional
+//! helpers described below assume `arrayvec`.
 //! 
+//! `typ` is given the size as a const generic argument, `typ<N>`, or `typ<T, N>` for vectors.
+//! Containers taking their capacity some other way are described with `typ_shape`, a string of
+//! the generic arguments where `{}` stands for the size and `{T}` for a vector's element type.
+//! `smallstr::SmallString` takes an array type instead of a const, so it's written as
+//! ```rust
+//! use fixed_size::fixed;
+//! use smallstr::SmallString;
+//! 
+//! #[fixed(s=4, typ=SmallString, typ_shape="<[u8; {}]>")]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let foo = Foo { s: SmallString::from("abcd") };
+//! assert_eq!(foo.s.inline_size(), 4);
+//! ```
+//! `typ_shape` only applies to the global `typ`, not to types given for a single field.
+//! 
+//! If all you need is for the value to be padded to its capacity when serialized, `serde_pad`
+//! generates such a wrapper for you, see [Padding](#padding).
+//! 
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(my_string=4)]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Foo {
+//!   my_string: String,
+//! }
+//! 
+//! let foo = Foo { my_string: ArrayString::<4>::from("abcd").unwrap() };
+//! // bincode actually supports var length strings but it's just used as an example and test
+//! let encoded = bincode::serialize(&foo).unwrap();
+//! let decoded: Foo = bincode::deserialize(&encoded[..]).unwrap();
+//! assert_eq!(foo, decoded);
+//! ```
+//! 
+//! Adding fewer than 4 characters to my_string will 0 pad the value. Adding more than
+//! 4 characters will result in an error.
+//! 
+//! `Box<str>` and `Cow<str>` fields are rewritten the same way as [`String`], dropping the
+//! lifetime of a `Cow` if nothing else uses it, as the fixed string is owned. Naming a field whose
+//! type isn't covered by one of the rules here is a compile error.
+//! 
+//! The macro only sees type names, so it can't tell that an alias like `type Str = String;`
+//! is a string. `force` rewrites every sized field of a type it doesn't recognize as a string,
+//! trusting that it is one. Forcing a field that isn't string-like gives type errors wherever it's
+//! used as its declared type, or in the generated code that treats it as a string.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! type Str = String;
+//! 
+//! #[fixed(name=8, force)]
+//! struct Foo {
+//!   name: Str,
+//! }
+//! 
+//! let foo = Foo { name: ArrayString::from("abc").unwrap() };
+//! assert_eq!(foo.name.capacity(), 8);
+//! ```
+//! 
+//! # Grouped fields
+//! Field sizes can also be grouped in `fields(...)`, leaving the options as siblings. Inside
+//! the group every key is a field, so fields named like an option, e.g. `typ`, can be sized.
+//! The flat and grouped forms can be mixed.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(fields(typ = 4, name = 8), gen_len)]
+//! struct Foo {
+//!   typ: String,
+//!   name: String,
+//! }
+//! 
+//! assert_eq!(Foo::FIXED_LEN, 12);
+//! ```
+//! 
+//! Or a size can sit on the field itself as `#[fixed_field(N)]`, taking the same options as
+//! `field=(N, ...)`. The attribute is removed along with the rest of the rewrite.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(gen_len)]
+//! struct Foo {
+//!   #[fixed_field(4)]
+//!   typ: String,
+//!   #[fixed_field(8, min=1)]
+//!   name: String,
+//! }
+//! 
+//! assert_eq!(Foo::FIXED_LEN, 12);
+//! ```
+//! 
+//! # Stacking
+//! Several `#[fixed]` attributes on one struct each rewrite their own fields. A field named
+//! again whose type is already fixed, e.g. an `ArrayString<4>` or a `[u8; N]`, is left as it is,
+//! unless `strict` is given, which makes it an error so stale sizes can be found. Helpers more
+//! than one of them needs, such as the error type of `gen_try_from`, are generated once, by the
+//! last. That is also the one to give options needing every field fixed, such as `gen_len`,
+//! since it sees the fields the others fixed.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(a=4)]
+//! #[fixed(a=2, b=8)]
+//! struct Foo {
+//!   a: String,
+//!   b: String,
+//! }
+//! 
+//! let foo = Foo { a: ArrayString::new(), b: ArrayString::new() };
+//! assert_eq!((foo.a.capacity(), foo.b.capacity()), (4, 8));
+//! ```
+//! 
+//! # Sibling structs
+//! `suffix=Fixed` leaves the original struct alone and emits a copy named with the suffix
+//! which has the fixed fields, along with a `TryFrom` conversion from the original and a `From`
+//! conversion back to it. This allows migrating a codebase one use at a time. Byte arrays
+//! convert back at their full length, including any zero padding.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, suffix=Fixed)]
+//! #[derive(Debug)]
+//! struct Foo {
+//!   s: String,
+//!   n: u32,
+//! }
+//! 
+//! let foo = Foo { s: "abcd".to_string(), n: 1 };
+//! let fixed = FooFixed::try_from(foo).unwrap();
+//! assert_eq!(fixed.s, ArrayString::<4>::from("abcd").unwrap());
+//! assert!(FooFixed::try_from(Foo { s: "abcde".to_string(), n: 1 }).is_err());
+//! let foo = Foo::from(fixed);
+//! assert_eq!(foo.s, "abcd");
+//! ```
+//! 
+//! # Fixed length
+//! With `gen_len` the struct gets a `FIXED_LEN` const holding the total length in bytes of its
+//! fields. Strings contribute their capacity, byte arrays their length and primitive numbers their
+//! size. Any other field is a compile error, since the struct doesn't have a fixed length.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, digest=12, gen_len)]
+//! struct Foo {
+//!   s: String,
+//!   digest: Vec<u8>,
+//! }
+//! 
+//! assert_eq!(Foo::FIXED_LEN, 16);
+//! ```
+//! 
+//! `gen_bytes` goes a step further and adds `to_bytes`, writing the fields in declaration order
+//! into a `[u8; FIXED_LEN]` with strings padded to their capacity, and `from_bytes` reading them
+//! back. Numbers and length prefixes are little-endian, unless given `endian=big` as is usual
+//! for network protocols.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, digest=2, gen_bytes)]
+//! struct Foo {
+//!   s: String,
+//!   n: u16,
+//!   digest: Vec<u8>,
+//! }
+//! 
+//! let foo = Foo { s: ArrayString::from("ab").unwrap(), n: 1, digest: [7, 8] };
+//! assert_eq!(foo.to_bytes(), *b"ab\0\0\x01\0\x07\x08");
+//! assert_eq!(Foo::from_bytes(&foo.to_bytes()).unwrap().s.as_str(), "ab");
+//! ```
+//! 
+//! For a `#[repr(C)]` struct whose fields are all byte arrays, `gen_bytes_ref` adds
+//! `from_bytes_ref`, borrowing a `&[u8; FIXED_LEN]` as the struct without copying, e.g. to
+//! parse records straight out of a mapped file. Any other field, or a `repr` other than a bare `C`,
+//! is a compile error.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(magic=4, payload=4, gen_bytes_ref, repr=C)]
+//! struct Record {
+//!   magic: Vec<u8>,
+//!   payload: Vec<u8>,
+//! }
+//! 
+//! let buf = b"FXSZdata and more";
+//! let record = Record::from_bytes_ref(buf[..Record::FIXED_LEN].try_into().unwrap());
+//! assert_eq!(&record.payload, b"data");
+//! ```
+//! 
+//! Framed formats often have a fixed header followed by a variable length tail. `tail(field)`
+//! keeps the last field's type as it is and leaves it out of `FIXED_LEN`, adding
+//! `header_len()`, the same as `FIXED_LEN`, and `total_len()` counting the tail too. With
+//! `gen_bytes` only the header is written, and `from_bytes` leaves the tail empty.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(magic=4, kind=2, tail(payload))]
+//! struct Frame {
+//!   magic: Vec<u8>,
+//!   kind: String,
+//!   payload: Vec<u8>,
+//! }
+//! 
+//! let frame = Frame { magic: *b"FRAM", kind: "ab".try_into().unwrap(), payload: vec![1, 2, 3] };
+//! assert_eq!(Frame::FIXED_LEN, 6);
+//! assert_eq!(frame.total_len(), 9);
+//! ```
+//! 
+//! # Schema
+//! `gen_schema` adds a `FIXED_FIELDS` const listing the name and size of each fixed field in
+//! declaration order, e.g. for a generic record dumper. Fields which weren't rewritten aren't
+//! listed, and tuple struct fields are named by their index.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(name=4, digest=16, gen_schema)]
+//! struct Foo {
+//!   name: String,
+//!   n: u32,
+//!   digest: Vec<u8>,
+//! }
+//! 
+//! assert_eq!(Foo::FIXED_FIELDS, &[("name", 4), ("digest", 16)]);
+//! ```
+//! 
+//! `gen_capacity` adds a `const fn <field>_capacity()` returning each fixed field's declared
+//! size instead, or `capacity_<index>` for tuple structs.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, digest=16, gen_capacity)]
+//! struct Foo {
+//!   s: String,
+//!   digest: Vec<u8>,
+//! }
+//! 
+//! const S: usize = Foo::s_capacity();
+//! assert_eq!((S, Foo::digest_capacity()), (4, 16));
+//! ```
+//! 
+//! # Padding
+//! `serde_pad` makes string fields use a generated `<Struct>Padded` wrapper around `ArrayString`
+//! which serializes exactly its capacity in bytes, padded with NUL, and trims trailing pad bytes
+//! when deserializing. It derefs to the `ArrayString`. Fields given their own type with `typ` are
+//! left alone.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, serde_pad)]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let foo = Foo { s: ArrayString::from("ab").unwrap().into() };
+//! let encoded = bincode::serialize(&foo).unwrap();
+//! assert_eq!(&encoded[8..], b"ab\0\0");
+//! let decoded: Foo = bincode::deserialize(&encoded[..]).unwrap();
+//! assert_eq!(foo, decoded);
+//! ```
+//! 
+//! `pad=' '` (or `pad=0x20`) pads with a different ASCII byte, and implies `serde_pad`. Like
+//! `align` and `trim` below it can be given for every field or for one as `field=(N, pad=' ')`,
+//! and the field's own setting wins; `gen_bytes` lays strings out the same way.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=8, pad=' ')]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let foo = Foo { s: ArrayString::from("ab").unwrap().into() };
+//! let encoded = bincode::serialize(&foo).unwrap();
+//! assert_eq!(&encoded[8..], b"ab      ");
+//! let decoded: Foo = bincode::deserialize(&encoded[..]).unwrap();
+//! assert_eq!(decoded.s.as_str(), "ab");
+//! ```
+//! 
+//! `align=right` pads on the left instead, and trims leading pad bytes when deserializing. It
+//! can be given for every field or for one as `field=(N, align=right)`, and implies `serde_pad`.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(amount=10, name=(4, align=left), align=right, pad=' ')]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Foo {
+//!   amount: String,
+//!   name: String,
+//! }
+//! 
+//! let foo = Foo { amount: ArrayString::from("42").unwrap().into(), name: ArrayString::from("ab").unwrap().into() };
+//! assert_eq!(serde_json::to_string(&foo).unwrap(), r#"{"amount":"        42","name":"ab  "}"#);
+//! ```
+//! 
+//! Where a value can legitimately end with the pad byte, `trim=none` keeps every byte read
+//! instead of trimming the padding. Like `align` it can be given for every field or for one as
+//! `field=(N, trim=none)`, and implies `serde_pad`.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, trim=none)]
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let foo: Foo = serde_json::from_str(r#"{"s":"ab\u0000\u0000"}"#).unwrap();
+//! assert_eq!(foo.s.as_str(), "ab\0\0");
+//! ```
+//! 
+//! `serde_with` keeps `String` fields as they are and pads them on the wire instead, through
+//! generated `serialize_<field>_fixed` and `deserialize_<field>_fixed` functions the fields are
+//! given `#[serde(serialize_with, deserialize_with)]` for. `pad`, `align` and `trim` apply to
+//! them as above, and a string longer than its size is a serde error. Like `no_rewrite` it
+//! also generates `validate`.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, serde_with)]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let foo = Foo { s: "ab".to_string() };
+//! let encoded = bincode::serialize(&foo).unwrap();
+//! assert_eq!(&encoded[8..], b"ab\0\0");
+//! let decoded: Foo = bincode::deserialize(&encoded[..]).unwrap();
+//! assert_eq!(foo, decoded);
+//! ```
+//! 
+//! # Wildcard
+//! `*=N` gives every string field (`String`, `Box<str>`, `Cow<str>` or `Option<String>`) which
+//! isn't listed a size of `N`. Other fields are left alone.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(*=10, name=4)]
+//! struct Foo {
+//!   name: String,
+//!   street: String,
+//!   city: Option<String>,
+//!   zip: u32,
+//!   photo: Vec<u8>,
+//! }
+//! 
+//! let foo = Foo { name: ArrayString::new(), street: ArrayString::new(), city: None, zip: 0, photo: vec![] };
+//! assert_eq!(foo.name.capacity(), 4);
+//! assert_eq!(foo.street.capacity(), 10);
+//! ```
+//! 
+//! # Named sizes
+//! A size can name a `const` instead of being a literal, anywhere a literal is accepted.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! const CALLSIGN_LEN: usize = 8;
+//! 
+//! #[fixed(callsign=CALLSIGN_LEN)]
+//! struct Pilot {
+//!   callsign: String,
+//! }
+//! 
+//! let pilot = Pilot { callsign: ArrayString::from("MAVERICK").unwrap() };
+//! assert_eq!(pilot.callsign.capacity(), CALLSIGN_LEN);
+//! ```
+//! 
+//! Sizes can also be arithmetic, e.g. `s=4+2` or `s=PREFIX_LEN + 8`. Arithmetic on literals is
+//! worked out by the macro and checked like a literal size.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! const PREFIX_LEN: usize = 2;
+//! 
+//! #[fixed(s=4+2, t=PREFIX_LEN + 8)]
+//! struct Foo {
+//!   s: String,
+//!   t: String,
+//! }
+//! 
+//! let foo = Foo { s: ArrayString::new(), t: ArrayString::new() };
+//! assert_eq!((foo.s.capacity(), foo.t.capacity()), (6, 10));
+//! ```
+//! 
+//! A named size can't be checked while expanding the macro, so a `const` of zero gives an empty
+//! field. `gen_static_asserts` emits a `const` assertion for each fixed field which fails the
+//! build at the struct naming the field instead.
+//! ```rust,compile_fail
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! const CALLSIGN_LEN: usize = 0;
+//! 
+//! #[fixed(callsign=CALLSIGN_LEN, gen_static_asserts)]
+//! struct Pilot {
+//!   callsign: String,
+//! }
+//! ```
+//! 
+//! The size can also be one of the struct's own const generic parameters.
+//! `gen_static_asserts` skips these fields, as the assertion can't name the parameter.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(payload=N)]
+//! struct Frame<const N: usize> {
+//!   payload: Vec<u8>,
+//! }
+//! 
+//! let frame = Frame::<8> { payload: [0; 8] };
+//! assert_eq!(frame.payload.len(), 8);
+//! ```
+//! 
+//! # Sizes from the environment
+//! `field=env("NAME")` reads the size from the environment variable `NAME` while the macro
+//! expands, for record widths configured per build. It can be used anywhere a literal size
+//! can, including in arithmetic. A variable which isn't set or doesn't hold a size is a compile
+//! error. Cargo doesn't rebuild when the variable changes on its own, so a build script should
+//! print `cargo:rerun-if-env-changed=NAME`.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=env("CARGO_PKG_VERSION_MAJOR"), t=env("CARGO_PKG_VERSION_MAJOR") * 8, gen_len)]
+//! struct Foo {
+//!   s: String,
+//!   t: String,
+//! }
+//! 
+//! let major: usize = env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap();
+//! assert_eq!(Foo::FIXED_LEN, 9 * major);
+//! ```
+//! 
+//! # Sizes from a file
+//! `schema="widths.txt"` reads sizes from a file, relative to the crate's `Cargo.toml`, holding
+//! one `field=size` per line as they'd be written in the attribute. Blank lines and lines
+//! starting with `#` are ignored. A size given in the attribute overrides the file's.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! // tests/widths.txt has `callsign=8`, `name=(4, min=2)` and `digest=16`
+//! #[fixed(schema="tests/widths.txt", name=6, skip(digest))]
+//! struct Pilot {
+//!   callsign: String,
+//!   name: String,
+//!   digest: Vec<u8>,
+//! }
+//! 
+//! let pilot = Pilot { callsign: ArrayString::new(), name: ArrayString::new(), digest: Vec::new() };
+//! assert_eq!((pilot.callsign.capacity(), pilot.name.capacity()), (8, 6));
+//! ```
+//! 
+//! # Nested structs
+//! The macro only rewrites the fields of the struct it's on, not of structs they contain, so a
+//! dotted key like `inner.code=4` is an error: put `#[fixed(code=4)]` on the inner struct. A
+//! field can instead be given the type of a struct which is already fixed, e.g. a sibling made
+//! with `suffix`, as `field: Declared -> Fixed`. The `TryFrom` conversion and `FIXED_LEN` are
+//! composed from the inner struct's.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(street=16, city=8, suffix=Fixed, gen_len)]
+//! struct Address {
+//!   street: String,
+//!   city: String,
+//! }
+//! 
+//! #[fixed(name=8, address: Address -> AddressFixed, suffix=Fixed, gen_len)]
+//! struct Person {
+//!   name: String,
+//!   address: Address,
+//! }
+//! 
+//! let person = Person {
+//!   name: "Ann".to_string(),
+//!   address: Address { street: "Main St".to_string(), city: "Springfield".to_string() },
+//! };
+//! assert!(PersonFixed::try_from(person).is_err());
+//! assert_eq!(PersonFixed::FIXED_LEN, 32);
+//! ```
+//! 
+//! # Layout
+//! `repr=C`, `repr=packed` or both as `repr=(C, packed)` add a `#[repr]` to the fixed struct, e.g.
+//! to map it directly onto memory. It's an error to also write a `#[repr]` on the struct.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, repr=(C, packed))]
+//! struct Foo {
+//!   s: String,
+//!   n: u32,
+//! }
+//! 
+//! assert_eq!(std::mem::align_of::<Foo>(), 1);
+//! ```
+//! 
+//! # Crate path
+//! By default the rewritten types are `::arrayvec::ArrayString` and `::arrayvec::ArrayVec`, so
+//! they needn't be imported, even for a struct declared in a function body. `crate=path` names
+//! them as `path::ArrayString` instead, and uses `path` for `arrayvec` items in generated code,
+//! for when it's re-exported or renamed.
+//! ```rust
+//! mod reexports {
+//!   pub use arrayvec::*;
+//! }
+//! 
+//! #[fixed_size::fixed(s=4, crate=reexports)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let foo = Foo { s: reexports::ArrayString::from("abcd").unwrap() };
+//! assert_eq!(foo.s.capacity(), 4);
+//! ```
+//! 
+//! # Naming the type
+//! `fixed_ty!` expands to the string type the attribute would use for a size, taking the same
+//! `typ` and `crate` options, for naming it outside a `#[fixed]` struct.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed_ty;
+//! 
+//! const LEN: usize = 8;
+//! 
+//! let s: fixed_ty!(4) = ArrayString::from("abcd").unwrap();
+//! let t: fixed_ty!(LEN, typ=heapless::String) = heapless::String::new();
+//! assert_eq!((s.capacity(), t.capacity()), (4, 8));
+//! ```
+//! 
+//! # Skipping fields
+//! `skip(notes, ...)` documents that fields are deliberately left variable length. They're left
+//! out of the wildcard, and naming a field which doesn't exist is an error as for sizes.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(*=8, skip(notes))]
+//! struct Foo {
+//!   name: String,
+//!   notes: String,
+//! }
+//! 
+//! let foo = Foo { name: ArrayString::new(), notes: "anything at all".to_string() };
+//! assert_eq!(foo.name.capacity(), 8);
+//! ```
+//! 
+//! # Serde names
+//! For formats where the wire names are spelled out, `require_rename` makes it an error for a
+//! fixed field not to carry a `#[serde(rename = "...")]`. The rename itself is left to serde.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(name=8, require_rename)]
+//! #[derive(serde::Serialize)]
+//! struct Foo {
+//!   #[serde(rename = "NAME")]
+//!   name: String,
+//!   notes: String,
+//! }
+//! 
+//! let foo = Foo { name: ArrayString::from("ann").unwrap(), notes: String::new() };
+//! assert_eq!(serde_json::to_string(&foo).unwrap(), r#"{"NAME":"ann","notes":""}"#);
+//! ```
+//! 
+//! A field can also be sized by its wire name as `serde:"name"=N`, which finds the field renamed
+//! to `name`, or the field called `name` if none is.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(serde:"my_string"=4, serde:"code"=2)]
+//! #[derive(serde::Deserialize)]
+//! struct Foo {
+//!   #[serde(rename = "my_string")]
+//!   name: String,
+//!   code: String,
+//! }
+//! 
+//! let foo: Foo = serde_json::from_str(r#"{"my_string":"abcd","code":"x"}"#).unwrap();
+//! assert_eq!((foo.name.capacity(), foo.code.capacity()), (4, 2));
+//! ```
+//! 
+//! # Tuple structs
+//! Fields of tuple structs are named by their index.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(0=4, 2=8)]
+//! struct Foo(String, u32, String);
+//! 
+//! let foo = Foo(ArrayString::from("abcd").unwrap(), 7, ArrayString::from("efgh").unwrap());
+//! assert_eq!(foo.2.capacity(), 8);
+//! ```
+//! 
+//! # Conditional fields
+//! A field behind `#[cfg(...)]` keeps it when rewritten, and the methods and impls generated for
+//! it carry the same `#[cfg]`, so they go away with the field. `FIXED_LEN` and the `to_bytes`
+//! layout only count the fields compiled in.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(name=8, debug_info=16, gen_len, gen_accessors)]
+//! struct Record {
+//!   name: String,
+//!   #[cfg(feature = "debug-info")]
+//!   debug_info: String,
+//! }
+//! 
+//! assert_eq!(Record::FIXED_LEN, 8);
+//! ```
+//! 
+//! # Setters
+//! Passing `gen_try_from` generates a `set_<field>` method for each fixed string field which
+//! builds the `ArrayString` from a `&str` and reports overflow instead of making callers
+//! construct it by hand. Setters and sibling conversions fail with a generated
+//! `<Struct>FixedError` naming the field, whichever type backs it. It's defined, along with the
+//! `Padded` and `Prefixed` wrappers, in a hidden `__fixed_<struct>` module and re-exported with
+//! the struct's visibility, so any number of structs can share a module.
 //! ```rust
 //! use arrayvec::ArrayString;
 //! use fixed_size::fixed;
 //! 
-//! #[fixed(my_string=4)]
+//! #[fixed(s=4, gen_try_from)]
+//! #[derive(Default)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let mut foo = Foo::default();
+//! foo.set_s("abcd").unwrap();
+//! assert_eq!(foo.s.as_str(), "abcd");
+//! assert_eq!(foo.set_s("abcde"), Err(FooFixedError::Overflow { field: "s", cap: 4, got: 5 }));
+//! ```
+//! 
+//! Setters fail on overflow by default. With `on_overflow=truncate` they instead keep as
+//! much of the value as fits, cutting on a `char` boundary. As they then can't fail, fields
+//! given a `min` can't be combined with it.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, gen_try_from, on_overflow=truncate)]
+//! #[derive(Default)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let mut foo = Foo::default();
+//! foo.set_s("abcdef");
+//! assert_eq!(foo.s.as_str(), "abcd");
+//! ```
+//! 
+//! `gen_mutators` adds a `<field>_try_push_str` method for each fixed string field, appending to
+//! it in place and failing with the same error if the result wouldn't fit.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, gen_mutators)]
+//! #[derive(Default)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let mut foo = Foo::default();
+//! foo.s_try_push_str("ab").unwrap();
+//! foo.s_try_push_str("cd").unwrap();
+//! assert_eq!(foo.s.as_str(), "abcd");
+//! assert_eq!(foo.s_try_push_str("e"), Err(FooFixedError::Overflow { field: "s", cap: 4, got: 5 }));
+//! ```
+//! 
+//! `gen_write` adds `<field>_writer` methods returning a `core::fmt::Write` which appends to the
+//! field, so it can be built up with `write!`. Writing more than fits is a `fmt::Error`.
+//! ```rust
+//! use core::fmt::Write;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=8, gen_write)]
+//! #[derive(Default)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let mut foo = Foo::default();
+//! write!(foo.s_writer(), "{}-{}", 12, 34).unwrap();
+//! assert_eq!(foo.s.as_str(), "12-34");
+//! assert!(write!(foo.s_writer(), "{}", 5678).is_err());
+//! ```
+//! 
+//! For numbers kept as text, as in many fixed width financial formats, `num_fmt=zero_pad`
+//! generates `set_<field>_num` methods writing any `Display` value right aligned and zero
+//! padded to the field's size, with the sign first, and without allocating. Given for one field as
+//! `field=(N, num_fmt=zero_pad)` it applies to just that field, and `num_fmt=none` opts one out.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(amount=10, num_fmt=zero_pad)]
+//! #[derive(Default)]
+//! struct Payment {
+//!   amount: String,
+//! }
+//! 
+//! let mut payment = Payment::default();
+//! payment.set_amount_num(42).unwrap();
+//! assert_eq!(payment.amount.as_str(), "0000000042");
+//! ```
+//! 
+//! # Validation
+//! A field given as `field=(N, min=M)` must be at least `M` long, in the same unit as its size. The limit isn't
+//! enforced on assignment, instead a `validate` method is generated which checks every such
+//! field, returning a generated `<Struct>FixedError` for the first that's too short.
+//! `Option` fields are only checked when they're `Some`.
+//! 
+//! With `validate_utf8`, `validate` and `from_bytes` also check that byte arrays hold UTF-8 up to
+//! the first pad byte, for formats where a bytes field is really a padded string.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(code=(4, min=4))]
+//! struct Airport {
+//!   code: String,
+//! }
+//! 
+//! let airport = Airport { code: ArrayString::from("LH").unwrap() };
+//! assert_eq!(airport.validate(), Err(AirportFixedError::TooShort { field: "code", min: 4, len: 2 }));
+//! let airport = Airport { code: ArrayString::from("EGLL").unwrap() };
+//! assert_eq!(airport.validate(), Ok(()));
+//! ```
+//! 
+//! # Declaring sizes only
+//! `no_rewrite` leaves the field types alone, for formats which already enforce the lengths.
+//! The sizes still feed `FIXED_LEN` and the other helpers, and `validate` returns an
+//! `Overflow` error for a field longer than its size. Options which only make sense for the
+//! rewritten types, such as `typ` or `gen_try_from`, are an error alongside it.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(code=4, gen_len, no_rewrite)]
+//! struct Airport {
+//!   code: String,
+//! }
+//! 
+//! let airport = Airport { code: "EGLL".to_string() };
+//! assert_eq!(airport.validate(), Ok(()));
+//! assert_eq!(Airport::FIXED_LEN, 4);
+//! let airport = Airport { code: "LHR-1".to_string() };
+//! assert_eq!(airport.validate(), Err(AirportFixedError::Overflow { field: "code", cap: 4, got: 5 }));
+//! ```
+//! 
+//! # Default
+//! `gen_default` implements `Default` with empty strings and vectors, `None` options and zeroed
+//! byte arrays of any length, instead of relying on `#[derive(Default)]` for the fixed types.
+//! Other fields use their own `Default`.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(name=4, digest=40, gen_default)]
+//! struct Foo {
+//!   name: String,
+//!   digest: Vec<u8>,
+//!   n: u32,
+//! }
+//! 
+//! let foo = Foo::default();
+//! assert_eq!((foo.name.as_str(), foo.digest, foo.n), ("", [0; 40], 0));
+//! ```
+//! 
+//! # Display
+//! `gen_display` implements `Display` by writing the fixed string fields in declaration order,
+//! separated by spaces, with the pad byte (NUL unless `pad` is given) trimmed from the end, or
+//! from the start with `align=right`.
+//! A `None` is written as nothing, and other fields are left out.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, gen_display)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let foo = Foo { s: ArrayString::from("ab\0\0").unwrap() };
+//! assert_eq!(foo.to_string(), "ab");
+//! ```
+//! 
+//! `gen_debug` implements `Debug` for developers instead, showing each fixed string, byte or
+//! vector field as its contents, pad bytes and all, followed by its capacity. Other fields are
+//! shown as `#[derive(Debug)]` would, which it replaces.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, gen_debug)]
+//! struct Foo {
+//!   s: String,
+//!   n: u8,
+//! }
+//! 
+//! let foo = Foo { s: ArrayString::from("ab").unwrap(), n: 1 };
+//! assert_eq!(format!("{:?}", foo), r#"Foo { s: "ab" (cap=4), n: 1 }"#);
+//! ```
+//! 
+//! `exclude_<feature>(...)` leaves fields out of what one `gen_<feature>` generates, here a
+//! secret kept out of `Debug` output. It works for `gen_accessors`, `gen_capacity`,
+//! `gen_debug`, `gen_display`, `gen_mutators`, `gen_str_eq` and `gen_write`.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(user=4, token=8, gen_debug, exclude_debug(token))]
+//! struct Login {
+//!   user: String,
+//!   token: String,
+//! }
+//! 
+//! let login = Login { user: ArrayString::from("ann").unwrap(), token: ArrayString::from("s3cr3t").unwrap() };
+//! assert_eq!(format!("{:?}", login), r#"Login { user: "ann" (cap=4) }"#);
+//! ```
+//! 
+//! # Comparing
+//! `gen_str_eq` generates a `<field>_eq` method for each fixed string field, comparing it to a
+//! `&str` with the pad byte trimmed as for `Display`. `Option` fields compare to an
+//! `Option<&str>`, and tuple fields are named `eq_0` and so on.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, nick=8, gen_str_eq)]
+//! struct Foo {
+//!   s: String,
+//!   nick: Option<String>,
+//! }
+//! 
+//! let foo = Foo { s: ArrayString::from("ab\0\0").unwrap(), nick: None };
+//! assert!(foo.s_eq("ab"));
+//! assert!(foo.nick_eq(None));
+//! ```
+//! 
+//! # Newtypes
+//! `gen_newtypes` wraps each fixed string field in a `#[repr(transparent)]` newtype of its own,
+//! named after the struct and the field, so that fields of the same size can't be mixed up. The
+//! newtypes deref to the string type and convert from it. With `serde_pad` or `serde_len`, which
+//! already assume `serde`, they also serialize as the string type. A newtype doesn't take the
+//! struct's generics, so a size naming one of its const parameters is an error.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(product_code=8, warehouse_code=8, gen_newtypes)]
+//! struct Stock {
+//!   product_code: String,
+//!   warehouse_code: String,
+//! }
+//! 
+//! let stock = Stock {
+//!   product_code: StockProductCode(ArrayString::from("P-1").unwrap()),
+//!   warehouse_code: ArrayString::from("W-1").unwrap().into(),
+//! };
+//! assert_eq!(stock.product_code.as_str(), "P-1");
+//! ```
+//! 
+//! ```rust,compile_fail
+//! # use arrayvec::ArrayString;
+//! # use fixed_size::fixed;
+//! # #[fixed(product_code=8, warehouse_code=8, gen_newtypes)]
+//! # struct Stock {
+//! #   product_code: String,
+//! #   warehouse_code: String,
+//! # }
+//! let code = StockProductCode(ArrayString::new());
+//! let stock = Stock { product_code: code.clone(), warehouse_code: code };
+//! ```
+//! 
+//! # Accessors
+//! `gen_accessors` generates a method borrowing each fixed string field as `&str` (or
+//! `Option<&str>`) and each byte field as `&[u8]`, named after the field, or `get_<index>` for
+//! tuple structs.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, digest=2, gen_accessors)]
+//! struct Foo {
+//!   s: String,
+//!   digest: Vec<u8>,
+//! }
+//! 
+//! let foo = Foo { s: ArrayString::from("abc").unwrap(), digest: [1, 2] };
+//! assert_eq!(foo.s(), "abc");
+//! assert_eq!(foo.digest(), &[1, 2]);
+//! ```
+//! 
+//! # Units
+//! Sizes are in bytes, as `ArrayString` counts them, so a field of size 4 can't hold `"éééé"`.
+//! With `unit=chars` string sizes count chars instead. The `ArrayString` is then sized for the
+//! worst case of 4 bytes per char, while the `TryFrom` conversion, generated setters and `min`
+//! check the number of chars, and `serde_pad` pads to that many chars. Assigning the field
+//! directly isn't checked.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(s=4, unit=chars, suffix=Fixed)]
+//! struct Foo {
+//!   s: String,
+//! }
+//! 
+//! let fixed = FooFixed::try_from(Foo { s: "éééé".to_string() }).unwrap();
+//! assert_eq!(fixed.s.capacity(), 16);
+//! assert!(FooFixed::try_from(Foo { s: "abcde".to_string() }).is_err());
+//! ```
+//! 
+//! For large buffers `unit=kib` counts every size, and `min`, in KiB, so `buf=64` is a 65536
+//! byte buffer.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(buf=64, unit=kib)]
+//! struct Foo {
+//!   buf: Vec<u8>,
+//! }
+//! 
+//! let foo = Foo { buf: [0; 65536] };
+//! assert_eq!(foo.buf.len(), 64 * 1024);
+//! ```
+//! 
+//! # Characters
+//! With `as_char`, `String` and `Box<str>` fields of size 1 become a `char` instead of an
+//! `ArrayString<1>`. Note a `char` holds one Unicode scalar value rather than one byte, so it
+//! can hold e.g. `'é'`, which an `ArrayString<1>` can't. It's serialized with serde's `char`
+//! support, as a one character string in JSON and as 1 to 4 bytes of UTF-8 in bincode. Use
+//! the default `ArrayString<1>` when exactly one byte is wanted.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(flag=1, as_char)]
 //! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
 //! struct Foo {
-//!   my_string: String,
+//!   flag: String,
 //! }
 //! 
-//! let foo = Foo { my_string: ArrayString::<4>::from("abcd").unwrap() };
-//! // bincode actually supports var length strings but it's just used as an example and test
+//! let foo = Foo { flag: 'é' };
+//! assert_eq!(serde_json::to_string(&foo).unwrap(), r#"{"flag":"é"}"#);
+//! ```
+//! 
+//! # Enums
+//! The macro can't tell that a path names an enum, so with `enum_as_int` a sized field of any
+//! other type is taken to be a C-like enum and stored as an integer of that many bytes, `u8` to
+//! `u64`. A `<field>` method converts it back with the enum's `TryFrom<uN>`, e.g. from
+//! `num_enum` or written by hand, and `set_<field>` stores a value's discriminant.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[derive(Clone, Copy, PartialEq, Debug)]
+//! enum Kind { Small = 1, Large = 2 }
+//! 
+//! impl TryFrom<u8> for Kind {
+//!   type Error = u8;
+//!   fn try_from(v: u8) -> Result<Self, u8> {
+//!     match v {
+//!       1 => Ok(Kind::Small),
+//!       2 => Ok(Kind::Large),
+//!       _ => Err(v),
+//!     }
+//!   }
+//! }
+//! 
+//! #[fixed(kind=1, enum_as_int)]
+//! struct Parcel {
+//!   kind: Kind,
+//! }
+//! 
+//! let mut parcel = Parcel { kind: 0 };
+//! parcel.set_kind(Kind::Large);
+//! assert_eq!(parcel.kind, 2);
+//! assert_eq!(parcel.kind(), Ok(Kind::Large));
+//! ```
+//! 
+//! # Optional strings
+//! `Option<String>` fields keep their [`Option`] and have the inner [`String`] replaced.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(nickname=12)]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Foo {
+//!   nickname: Option<String>,
+//! }
+//! 
+//! for foo in [Foo { nickname: None }, Foo { nickname: Some(ArrayString::<12>::from("abc").unwrap()) }] {
+//!     let encoded = bincode::serialize(&foo).unwrap();
+//!     let decoded: Foo = bincode::deserialize(&encoded[..]).unwrap();
+//!     assert_eq!(foo, decoded);
+//! }
+//! ```
+//! 
+//! # Map keys
+//! Prost maps become [`HashMap<String, V>`](std::collections::HashMap) or `BTreeMap`. Their key
+//! is fixed by naming the field with a `_key` suffix, so `labels_key=8` turns `labels` into a
+//! `HashMap<ArrayString<8>, V>`. The value is left alone.
+//! ```rust
+//! use arrayvec::ArrayString;
+//! use fixed_size::fixed;
+//! use std::collections::HashMap;
+//! 
+//! #[fixed(labels_key=8)]
+//! struct Foo {
+//!   labels: HashMap<String, u32>,
+//! }
+//! 
+//! let foo = Foo { labels: HashMap::from([(ArrayString::from("env").unwrap(), 1)]) };
+//! assert_eq!(foo.labels.keys().next().unwrap().capacity(), 8);
+//! ```
+//! 
+//! # Byte vectors
+//! Prost uses [`Vec<u8>`] for `bytes` fields. Naming one of these fields replaces it
+//! with a `[u8; N]` array, or `typ<u8, N>` when `typ` is given.
+//! `bytes::Bytes` fields are treated the same way, matched by the name `Bytes` however they're
+//! imported. Any other [`Vec<T>`] becomes an `ArrayVec<T, N>`, again honouring `typ`.
+//! 
+//! `typ` applies to strings and vectors alike. To give them different containers, use
+//! `str_typ` and `vec_typ`, each of which takes precedence over `typ` for its kind of field.
+//! ```rust
+//! use arrayvec::ArrayVec;
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(name=8, digest=4, str_typ=heapless::String, vec_typ=ArrayVec)]
+//! struct Foo {
+//!   name: String,
+//!   digest: Vec<u8>,
+//! }
+//! 
+//! let foo = Foo { name: heapless::String::new(), digest: ArrayVec::new() };
+//! assert_eq!((foo.name.capacity(), foo.digest.capacity()), (8, 4));
+//! ```
+//! 
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(checksum=16)]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Foo {
+//!   checksum: Vec<u8>,
+//! }
+//! 
+//! let foo = Foo { checksum: *b"0123456789abcdef" };
 //! let encoded = bincode::serialize(&foo).unwrap();
+//! assert_eq!(encoded.len(), 16);
 //! let decoded: Foo = bincode::deserialize(&encoded[..]).unwrap();
 //! assert_eq!(foo, decoded);
 //! ```
 //! 
-//! Adding fewer than 4 characters to my_string will 0 pad the value. Adding more than
-//! 4 characters will result in an error.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(tag=4)]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Foo {
+//!   tag: bytes::Bytes,
+//! }
+//! 
+//! let foo = Foo { tag: *b"abcd" };
+//! let encoded = bincode::serialize(&foo).unwrap();
+//! assert_eq!(encoded, b"abcd");
+//! let decoded: Foo = bincode::deserialize(&encoded[..]).unwrap();
+//! assert_eq!(foo, decoded);
+//! ```
+//! 
+//! ```rust
+//! use arrayvec::ArrayVec;
+//! use fixed_size::fixed;
+//! 
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Point { x: i32, y: i32 }
+//! 
+//! #[fixed(points=3)]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Path {
+//!   points: Vec<Point>,
+//! }
+//! 
+//! let mut path = Path { points: ArrayVec::new() };
+//! path.points.push(Point { x: 1, y: 2 });
+//! let encoded = bincode::serialize(&path).unwrap();
+//! let decoded: Path = bincode::deserialize(&encoded[..]).unwrap();
+//! assert_eq!(path, decoded);
+//! ```
+//! 
+//! `Option<Vec<u8>>` and `Option<Bytes>` keep their [`Option`] and have the bytes replaced, so
+//! `sig=64` makes an `Option<[u8; 64]>`.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(sig=4)]
+//! struct Foo {
+//!   sig: Option<Vec<u8>>,
+//! }
+//! 
+//! let foo = Foo { sig: Some(*b"abcd") };
+//! assert_eq!(foo.sig.map(|s| s.len()), Some(4));
+//! ```
+//! 
+//! A `Vec<String>` with one size keeps its strings. Giving a second size, `tags=(4, 16)`, also
+//! fixes each string, making an `ArrayVec<ArrayString<16>, 4>`. A `min` then applies to each
+//! string.
+//! ```rust
+//! use arrayvec::{ArrayString, ArrayVec};
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(tags=(4, 16))]
+//! struct Foo {
+//!   tags: Vec<String>,
+//! }
+//! 
+//! let mut foo = Foo { tags: ArrayVec::new() };
+//! foo.tags.push(ArrayString::from("urgent").unwrap());
+//! assert_eq!((foo.tags.capacity(), foo.tags[0].capacity()), (4, 16));
+//! ```
+//! 
+//! # Length prefixed bytes
+//! With `serde_len`, byte vectors keep their length instead of becoming arrays. They're
+//! rewritten to a generated `<Struct>Prefixed<N>`, which wraps an `ArrayVec<u8, N>` and
+//! serializes a `u32` length followed by the bytes, padded with zeros to `N`. Deserializing a
+//! length greater than `N` is an error.
+//! ```rust
+//! use fixed_size::fixed;
+//! 
+//! #[fixed(payload=8, serde_len)]
+//! #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+//! struct Packet {
+//!   payload: Vec<u8>,
+//! }
+//! 
+//! let packet = Packet { payload: PacketPrefixed::try_from(&b"abc"[..]).unwrap() };
+//! let encoded = bincode::serialize(&packet).unwrap();
+//! assert_eq!(encoded.len(), 4 + 8);
+//! let decoded: Packet = bincode::deserialize(&encoded[..]).unwrap();
+//! assert_eq!(&decoded.payload[..], b"abc");
+//! ```
+//! 
+//! # Other macros
+//! The macros are implemented in the `fixed-size-core` crate, which other proc macros can
+//! depend on to expand `#[fixed]` themselves, or to rewrite a single field with
+//! `fixed_size_core::transform_field`.
 
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use quote::quote;
-use std::collections::HashMap;
-use syn::{parse::{Parse, ParseStream, Result}, Token, punctuated::Punctuated,
-                  fold::Fold, Expr, ExprAssign, Ident, LitInt, Lit, parse_macro_input,
-                  ItemStruct, Type, Field, parse_quote};
-
-type MapType = HashMap<Ident, LitInt>;
-struct Args {
-    size_map: MapType,
-    typ: Ident,
-}
-
-const ERRMSG: &str = "Must specify an Ident=Int or typ=Structname";
-
-impl Parse for Args {
-    fn parse(input: ParseStream) -> Result<Self> {
-        let vars = Punctuated::<ExprAssign, Token![,]>::parse_terminated(input)?;
-        let mut size_map = MapType::new();
-        let mut typ = Ident::new("ArrayString", Span::mixed_site());
-        for var in vars.into_iter() {
-            match (&*var.left, &*var.right) {
-                (Expr::Path(p), Expr::Lit(v)) => {
-                    let key = p.path.get_ident().unwrap();
-                    if let Lit::Int(num) = &v.lit {
-                        size_map.insert(key.clone(), num.clone());
-                    } else {
-                        return Err(input.error(ERRMSG));
-                    }
-                },
-                (Expr::Path(p), Expr::Path(v)) => {
-                    let key = p.path.get_ident().unwrap();
-                    if key.to_string() != "typ" {
-                        return Err(input.error(ERRMSG));
-                    }
-                    if let Some(val) = v.path.get_ident() {
-                        typ = val.clone();
-                    } else {
-                        return Err(input.error(ERRMSG));
-                    }
-                }
-                (_, _) => {
-                    return Err(input.error(ERRMSG));
-                }
-            }
-        }
-
-        Ok(Args { size_map, typ })
-    }
-}
-
-impl Fold for Args {
-    fn fold_field(&mut self, input: Field) -> syn::Field {
-        if let Some(key) = &input.ident {
-            let typ = &self.typ;
-            if let Some(num) = self.size_map.get(key) {
-                if let Type::Path(p) = &input.ty {
-                    if p.path.is_ident("String") || p.path.segments.last().unwrap().ident.to_string() == "String" {
-                        return Field {
-                            attrs: input.attrs,
-                            vis: input.vis,
-                            mutability: input.mutability,
-                            ident: input.ident,
-                            colon_token: input.colon_token,
-                            ty: parse_quote!{#typ::<#num>},
-                        };
-                    }
-                }
-            }
-        }
-        input
-    }
-}
 
 /// Replace one or more variable length fields with a fixed length equivalent
 /// 
 /// Pass in a list of `field_name=length` arguments. Optionally
-/// pass `typ=MyType` to use a different type for the replacement. See
+/// pass `typ=MyType` to use a different type for the replacement, or
+/// `field_name=(length, MyType)` to change it for one field. See
 /// the crate documentation for moreinformation.
 #[proc_macro_attribute]
 pub fn fixed(args: TokenStream, input: TokenStream) -> TokenStream {
-    let mut args = parse_macro_input!(args as Args);
-    let input = parse_macro_input!(input as ItemStruct);
-    let output = args.fold_item_struct(input);
-    proc_macro::TokenStream::from(quote!(#output))
-}
\ No newline at end of file
+    fixed_size_core::fixed(args.into(), input.into()).into()
+}
+
+/// Name the fixed length string type `#[fixed]` would use, e.g. `fixed_ty!(4)` for `ArrayString<4>`
+/// 
+/// The size may be followed by `typ=MyType`, `typ_shape="<...>"` and `crate=path`, as for the
+/// attribute.
+#[proc_macro]
+pub fn fixed_ty(input: TokenStream) -> TokenStream {
+    fixed_size_core::fixed_ty(input.into()).into()
+}
@@ -0,0 +1,49 @@
+use arrayvec::{ArrayString, ArrayVec};
+use fixed_size::fixed;
+
+#[fixed(s=4, nick=4, digest=2, payload=4, points=2, gen_accessors, serde_len)]
+#[derive(Default)]
+struct Foo {
+    s: String,
+    nick: Option<String>,
+    digest: Vec<u8>,
+    payload: Vec<u8>,
+    points: Vec<u32>,
+}
+
+#[fixed(s=4, r#type=4, serde_pad, gen_accessors)]
+#[derive(Default)]
+struct Padded {
+    s: String,
+    r#type: Option<String>,
+}
+
+#[fixed(0=4, gen_accessors)]
+struct Pair(String, u8);
+
+#[test]
+fn accessors() {
+    let mut foo = Foo { s: ArrayString::from("abc").unwrap(), ..Default::default() };
+    foo.payload.push(7);
+    assert_eq!(foo.s(), "abc");
+    assert_eq!(foo.nick(), None);
+    assert_eq!(foo.digest(), &[] as &[u8]);
+    assert_eq!(foo.payload(), &[7]);
+    let _: &ArrayVec<u32, 2> = &foo.points;
+}
+
+#[test]
+fn wrapped_accessors() {
+    let mut p = Padded::default();
+    p.s.push_str("ab");
+    p.r#type = Some(ArrayString::from("x").unwrap().into());
+    assert_eq!(p.s(), "ab");
+    assert_eq!(p.r#type(), Some("x"));
+}
+
+#[test]
+fn tuple_accessor() {
+    let p = Pair(ArrayString::from("ab").unwrap(), 1);
+    assert_eq!(p.get_0(), "ab");
+    assert_eq!(p.1, 1);
+}
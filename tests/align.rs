@@ -0,0 +1,78 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(amount=10, code=(4, align=right), name=4, align=left, pad=' ')]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Mixed {
+    amount: String,
+    code: String,
+    name: Option<String>,
+}
+
+#[fixed(amount=(10, align=right), pad='0')]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Zeroes {
+    amount: String,
+}
+
+#[fixed(amount=(10, align=right), pad=' ')]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Amount {
+    amount: String,
+}
+
+#[fixed(amount=(10, align=right, pad=' '), name=(6, pad=0), code=2, pad='*', gen_bytes)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Record {
+    amount: String,
+    name: String,
+    code: String,
+}
+
+fn s<const N: usize, const P: u8, const R: bool>(v: &str) -> MixedPadded<N, P, R> {
+    ArrayString::from(v).unwrap().into()
+}
+
+fn record() -> Record {
+    let (amount, name) = (ArrayString::from("42").unwrap(), ArrayString::from("bob").unwrap());
+    Record { amount: amount.into(), name: name.into(), code: ArrayString::new().into() }
+}
+
+#[test]
+fn right_aligned_pads_on_the_left() {
+    let a = Amount { amount: ArrayString::from("42").unwrap().into() };
+    assert_eq!(serde_json::to_string(&a).unwrap(), r#"{"amount":"        42"}"#);
+    let encoded = bincode::serialize(&a).unwrap();
+    assert_eq!(&encoded[8..], b"        42");
+    assert_eq!(bincode::deserialize::<Amount>(&encoded).unwrap(), a);
+}
+
+#[test]
+fn per_field_alignment() {
+    let m = Mixed { amount: s("42"), code: s("ab"), name: Some(s("cd")) };
+    let json = serde_json::to_string(&m).unwrap();
+    assert_eq!(json, r#"{"amount":"42        ","code":"  ab","name":"cd  "}"#);
+    assert_eq!(serde_json::from_str::<Mixed>(&json).unwrap(), m);
+}
+
+#[test]
+fn leading_pad_is_trimmed() {
+    let z: Zeroes = serde_json::from_str(r#"{"amount":"0000001234"}"#).unwrap();
+    assert_eq!(z.amount.as_str(), "1234");
+    assert_eq!(serde_json::to_string(&z).unwrap(), r#"{"amount":"0000001234"}"#);
+}
+
+#[test]
+fn per_field_pad() {
+    let r = record();
+    let json = serde_json::to_string(&r).unwrap();
+    assert_eq!(json, r#"{"amount":"        42","name":"bob\u0000\u0000\u0000","code":"**"}"#);
+    assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), r);
+}
+
+#[test]
+fn per_field_pad_in_bytes() {
+    let r = record();
+    assert_eq!(r.to_bytes(), *b"        42bob\0\0\0**");
+    assert_eq!(Record::from_bytes(&r.to_bytes()).unwrap(), r);
+}
@@ -0,0 +1,40 @@
+use fixed_size::fixed;
+
+const LEN: usize = 4;
+
+#[fixed(typ=heapless::String, s=LEN, t=8)]
+struct TypFirst {
+    s: String,
+    t: String,
+}
+
+#[fixed(s=LEN, t=8, typ=heapless::String)]
+struct TypLast {
+    s: String,
+    t: String,
+}
+
+#[fixed(s=LEN, typ=heapless::String, t=8, on_overflow=truncate, gen_try_from)]
+#[derive(Default)]
+struct TypBetween {
+    s: String,
+    t: String,
+}
+
+#[fixed(fields(typ=LEN), typ=heapless::String)]
+#[derive(Default)]
+struct FieldNamedTyp {
+    typ: String,
+}
+
+#[test]
+fn typ_order_doesnt_matter() {
+    let first = TypFirst { s: heapless::String::try_from("ab").unwrap(), t: heapless::String::new() };
+    let last = TypLast { s: first.s.clone(), t: first.t.clone() };
+    let (s, t): (heapless::String<LEN>, heapless::String<8>) = (last.s, last.t);
+    assert_eq!((s.capacity(), t.capacity()), (4, 8));
+    let mut between = TypBetween::default();
+    between.set_s("abcdef");
+    assert_eq!(between.s.as_str(), "abcd");
+    assert_eq!(FieldNamedTyp::default().typ.capacity(), 4);
+}
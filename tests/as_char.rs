@@ -0,0 +1,56 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(flag=1, other=1, name=4, wide=(1, ArrayString), as_char, suffix=Fixed, gen_accessors)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug, Clone)]
+struct Record {
+    flag: String,
+    other: Box<str>,
+    name: String,
+    wide: String,
+}
+
+#[fixed(flag=1)]
+struct Default1 {
+    flag: String,
+}
+
+fn record(flag: &str) -> Record {
+    Record { flag: flag.to_string(), other: "x".into(), name: "ab".to_string(), wide: "y".to_string() }
+}
+
+#[test]
+fn ascii_char() {
+    let fixed = RecordFixed::try_from(record("Y")).unwrap();
+    assert_eq!(fixed.flag(), 'Y');
+    assert_eq!(fixed.other, 'x');
+    assert_eq!(fixed.name.as_str(), "ab");
+    assert_eq!(fixed.wide.capacity(), 1);
+    let encoded = bincode::serialize(&fixed).unwrap();
+    assert_eq!(encoded[0], b'Y');
+    assert_eq!(bincode::deserialize::<RecordFixed>(&encoded).unwrap(), fixed);
+}
+
+#[test]
+fn multi_byte_char() {
+    let fixed = RecordFixed::try_from(record("é")).unwrap();
+    assert_eq!(fixed.flag, 'é');
+    let encoded = bincode::serialize(&fixed).unwrap();
+    assert_eq!(&encoded[..2], "é".as_bytes());
+    let json = serde_json::to_string(&fixed).unwrap();
+    assert!(json.starts_with(r#"{"flag":"é""#), "{}", json);
+    assert_eq!(serde_json::from_str::<RecordFixed>(&json).unwrap(), fixed);
+    assert_eq!(Record::from(fixed), record("é"));
+}
+
+#[test]
+fn not_one_char_is_an_error() {
+    assert!(RecordFixed::try_from(record("")).is_err());
+    assert!(RecordFixed::try_from(record("ab")).is_err());
+}
+
+#[test]
+fn default_stays_array_string() {
+    let d = Default1 { flag: ArrayString::from("a").unwrap() };
+    assert_eq!(d.flag.capacity(), 1);
+}
@@ -0,0 +1,14 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(name=8)]
+#[derive(Debug, PartialEq)]
+struct Foo {
+    name: Box<str>,
+}
+
+#[test]
+fn box_str_becomes_array_string() {
+    let foo = Foo { name: ArrayString::<8>::from("abc").unwrap() };
+    assert_eq!(foo.name.capacity(), 8);
+}
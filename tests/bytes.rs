@@ -0,0 +1,110 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(city=4, suffix=Fixed, gen_bytes)]
+#[derive(Debug)]
+struct Address {
+    city: String,
+    zip: u32,
+}
+
+#[fixed(name=6, digest=4, address: Address -> AddressFixed, suffix=Fixed, gen_bytes)]
+#[derive(Debug)]
+struct Record {
+    name: String,
+    address: Address,
+    digest: Vec<u8>,
+    n: i64,
+}
+
+#[fixed(tag=3, serde_len, gen_bytes)]
+#[derive(Debug, PartialEq)]
+struct Tagged {
+    tag: Vec<u8>,
+}
+
+fn record() -> RecordFixed {
+    RecordFixed {
+        name: ArrayString::from("frodo").unwrap(),
+        address: AddressFixed { city: ArrayString::from("Bag").unwrap(), zip: 0x01020304 },
+        digest: [1, 2, 3, 4],
+        n: -2,
+    }
+}
+
+#[test]
+fn round_trip() {
+    let b = record().to_bytes();
+    assert_eq!(b.len(), RecordFixed::FIXED_LEN);
+    assert_eq!(&b[..14], b"frodo\0Bag\0\x04\x03\x02\x01");
+    assert_eq!(&b[18..], &(-2i64).to_le_bytes());
+    let r = RecordFixed::from_bytes(&b).unwrap();
+    assert_eq!(r.name.as_str(), "frodo");
+    assert_eq!(r.address.city.as_str(), "Bag");
+    assert_eq!(r.address.zip, 0x01020304);
+    assert_eq!(r.digest, [1, 2, 3, 4]);
+    assert_eq!(r.n, -2);
+}
+
+#[test]
+fn bad_utf8() {
+    let mut b = record().to_bytes();
+    b[0] = 0xff;
+    assert_eq!(RecordFixed::from_bytes(&b).unwrap_err().to_string(), "field `name` isn't valid UTF-8");
+    let mut b = record().to_bytes();
+    b[6] = 0xff;
+    assert_eq!(RecordFixed::from_bytes(&b).unwrap_err().to_string(), "field `address` couldn't be converted");
+}
+
+#[test]
+fn length_prefixed() {
+    let t = Tagged { tag: TaggedPrefixed::try_from(&[9u8][..]).unwrap() };
+    let b = t.to_bytes();
+    assert_eq!(b, [1, 0, 0, 0, 9, 0, 0]);
+    assert_eq!(Tagged::from_bytes(&b).unwrap(), t);
+}
+
+#[test]
+fn long_prefix() {
+    let mut b = [0u8; Tagged::FIXED_LEN];
+    b[0] = 4;
+    assert_eq!(Tagged::from_bytes(&b).unwrap_err().to_string(), "field `tag` has length 4, expected at most 3");
+}
+
+#[fixed(id=2, endian=big, gen_bytes)]
+#[derive(Debug, PartialEq)]
+struct Big {
+    id: String,
+    seq: u32,
+}
+
+#[fixed(id=2, endian=little, gen_bytes)]
+#[derive(Debug, PartialEq)]
+struct Little {
+    id: String,
+    seq: u32,
+}
+
+#[test]
+fn byte_order() {
+    let big = Big { id: ArrayString::from("ab").unwrap(), seq: 0x01020304 };
+    assert_eq!(big.to_bytes(), *b"ab\x01\x02\x03\x04");
+    assert_eq!(Big::from_bytes(&big.to_bytes()).unwrap(), big);
+    let little = Little { id: ArrayString::from("ab").unwrap(), seq: 0x01020304 };
+    assert_eq!(little.to_bytes(), *b"ab\x04\x03\x02\x01");
+    assert_eq!(Little::from_bytes(&little.to_bytes()).unwrap(), little);
+}
+
+#[fixed(s=(4, trim=none), t=4, serde_pad, gen_bytes)]
+struct Kept {
+    s: String,
+    t: String,
+}
+
+#[test]
+fn trim_none_keeps_pad_bytes() {
+    let kept = Kept::from_bytes(b"ab\0\0cd\0\0").unwrap();
+    assert_eq!(kept.s.as_bytes(), b"ab\0\0");
+    assert_eq!(kept.t.as_bytes(), b"cd");
+    assert_eq!(bincode::serialize(&kept.s).unwrap()[8..], *b"ab\0\0");
+}
@@ -0,0 +1,34 @@
+use fixed_size::fixed;
+
+#[fixed(magic=4, version=2, payload=10, gen_bytes_ref, repr=C)]
+#[derive(Debug)]
+struct Header {
+    magic: Vec<u8>,
+    version: Vec<u8>,
+    payload: Vec<u8>,
+}
+
+#[fixed(id=3, crc=2, gen_bytes_ref)]
+#[repr(C)]
+struct Tag {
+    id: Vec<u8>,
+    crc: Vec<u8>,
+}
+
+#[test]
+fn parses_out_of_a_slice_without_copying() {
+    let buf: Vec<u8> = b"FXSZ\x01\x00hello worldtrailing".to_vec();
+    let bytes: &[u8; Header::FIXED_LEN] = buf[..Header::FIXED_LEN].try_into().unwrap();
+    let header = Header::from_bytes_ref(bytes);
+    assert_eq!(&header.magic, b"FXSZ");
+    assert_eq!(header.version, [1, 0]);
+    assert_eq!(&header.payload, b"hello worl");
+    assert_eq!(header as *const Header as *const u8, buf.as_ptr());
+}
+
+#[test]
+fn repr_attribute_is_allowed() {
+    assert_eq!(Tag::FIXED_LEN, 5);
+    let tag = Tag::from_bytes_ref(b"abc\x12\x34");
+    assert_eq!((tag.id, tag.crc), (*b"abc", [0x12, 0x34]));
+}
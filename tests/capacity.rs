@@ -0,0 +1,20 @@
+use fixed_size::fixed;
+
+#[fixed(s=4, nick=(3, min=1), tags=(2, 5), name=2, unit=chars, gen_capacity)]
+struct Foo {
+    s: String,
+    nick: Option<String>,
+    tags: Vec<String>,
+    name: String,
+    n: u32,
+}
+
+#[fixed(0=8, 1=2, gen_capacity)]
+struct Tuple(String, Vec<u8>, u8);
+
+#[test]
+fn capacity_is_the_declared_size() {
+    assert_eq!(Foo::s_capacity(), 4);
+    assert_eq!((Foo::nick_capacity(), Foo::tags_capacity(), Foo::name_capacity()), (3, 2, 2));
+    assert_eq!((Tuple::capacity_0(), Tuple::capacity_1()), (8, 2));
+}
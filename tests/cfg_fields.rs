@@ -0,0 +1,62 @@
+use fixed_size::fixed;
+
+// `cfg(test)` stands in for an enabled feature and `cfg(not(test))` for a disabled one, so both
+// states are checked in one build
+#[fixed(code=4, on=8, off=8, gen_len, gen_schema, gen_capacity, gen_accessors, gen_try_from, gen_mutators, gen_str_eq, gen_display, gen_debug, gen_default, gen_static_asserts)]
+struct Record {
+    code: String,
+    #[cfg(test)]
+    on: String,
+    #[cfg(not(test))]
+    off: String,
+}
+
+#[fixed(name=4, tag=2, gen_bytes, suffix=Fixed)]
+#[derive(Debug, PartialEq)]
+struct Packet {
+    #[cfg(not(test))]
+    name: String,
+    tag: String,
+    n: u16,
+}
+
+#[fixed(code=(4, min=2), off=(8, min=2), serde_with)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Wire {
+    code: String,
+    #[cfg(not(test))]
+    off: String,
+}
+
+#[test]
+fn enabled_fields_keep_their_helpers() {
+    let mut record = Record::default();
+    record.set_code("ab").unwrap();
+    record.set_on("cd").unwrap();
+    record.on_try_push_str("ef").unwrap();
+    assert_eq!(record.on(), "cdef");
+    assert!(record.on_eq("cdef"));
+    assert_eq!(Record::on_capacity(), 8);
+    assert_eq!(record.to_string(), "ab cdef");
+    assert_eq!(format!("{:?}", record), r#"Record { code: "ab" (cap=4), on: "cdef" (cap=8) }"#);
+}
+
+#[test]
+fn disabled_fields_are_left_out() {
+    assert_eq!(Record::FIXED_LEN, 12);
+    assert_eq!(Record::FIXED_FIELDS, &[("code", 4), ("on", 8)]);
+    let packet = Packet { tag: "ab".to_string(), n: 7 };
+    let fixed = PacketFixed::try_from(packet).unwrap();
+    assert_eq!(PacketFixed::FIXED_LEN, 4);
+    assert_eq!(fixed.to_bytes(), *b"ab\x07\0");
+    let back = PacketFixed::from_bytes(&fixed.to_bytes()).unwrap();
+    assert_eq!(Packet::from(back), Packet { tag: "ab".to_string(), n: 7 });
+}
+
+#[test]
+fn disabled_fields_skip_serde_and_validate() {
+    let wire = Wire { code: "ab".to_string() };
+    assert_eq!(wire.validate(), Ok(()));
+    assert_eq!(serde_json::to_string(&wire).unwrap(), r#"{"code":"ab\u0000\u0000"}"#);
+    assert_eq!(Wire { code: "a".to_string() }.validate(), Err(WireFixedError::TooShort { field: "code", min: 2, len: 1 }));
+}
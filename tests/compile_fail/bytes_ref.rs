@@ -0,0 +1,20 @@
+use fixed_size::fixed;
+
+#[fixed(id=4, gen_bytes_ref)]
+struct NotC {
+    id: Vec<u8>,
+}
+
+#[fixed(id=4, name=8, gen_bytes_ref, repr=C)]
+struct Text {
+    id: Vec<u8>,
+    name: String,
+}
+
+#[fixed(id=4, gen_bytes_ref)]
+#[repr(C, align(8))]
+struct Aligned {
+    id: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use fixed_size::fixed;
+
+#[fixed(s=4, on_overflow=truncate)]
+struct Truncated {
+    s: String,
+}
+
+#[fixed(flag=(1, align=right), as_char)]
+struct Flag {
+    flag: String,
+}
+
+#[fixed(id=2, endian=big)]
+struct Id {
+    id: Vec<u8>,
+}
+
+#[fixed(code=(4, min=2), gen_try_from, on_overflow=truncate)]
+struct Code {
+    code: String,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use fixed_size::fixed;
+
+struct Inner {
+    code: String,
+}
+
+#[fixed(name=4, inner.code=4)]
+struct Outer {
+    name: String,
+    inner: Inner,
+}
+
+#[fixed(fields(name=4, inner.deeper.code=2))]
+struct Grouped {
+    name: String,
+    inner: Inner,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(s=4, s=8)]
+struct Foo {
+    s: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use fixed_size::fixed;
+
+#[fixed(s=4, repr=C)]
+#[repr(C)]
+struct Foo {
+    s: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(typ=MyString, s=4, typ=OtherString)]
+struct Foo {
+    s: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(s=4)]
+enum Foo {
+    S(String),
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use fixed_size::fixed;
+
+enum Kind {
+    A,
+}
+
+#[fixed(kind=3, enum_as_int)]
+struct Odd {
+    kind: Kind,
+}
+
+#[fixed(kind=1, enum_as_int, suffix=Fixed)]
+struct Sibling {
+    kind: Kind,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use fixed_size::fixed;
+
+#[fixed(s=env("FIXED_SIZE_TEST_UNSET_LEN"))]
+struct Unset {
+    s: String,
+}
+
+#[fixed(s=env("CARGO_PKG_NAME"))]
+struct NotANumber {
+    s: String,
+}
+
+#[fixed(s=env(REC_LEN))]
+struct NotAString {
+    s: String,
+}
+
+fn main() {}
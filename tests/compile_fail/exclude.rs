@@ -0,0 +1,18 @@
+use fixed_size::fixed;
+
+#[fixed(a=4, exclude_debug(a))]
+struct NotGenerated {
+    a: String,
+}
+
+#[fixed(a=4, gen_debug, exclude_debug(b))]
+struct NoSuchField {
+    a: String,
+}
+
+#[fixed(a=4, gen_debug, exclude_serde(a))]
+struct UnknownFeature {
+    a: String,
+}
+
+fn main() {}
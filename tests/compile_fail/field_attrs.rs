@@ -0,0 +1,21 @@
+use fixed_size::fixed;
+
+#[fixed(s=4)]
+struct Twice {
+    #[fixed_field(8)]
+    s: String,
+}
+
+#[fixed(skip(s))]
+struct Skipped {
+    #[fixed_field(8)]
+    s: String,
+}
+
+#[fixed]
+struct Malformed {
+    #[fixed_field]
+    s: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use fixed_size::fixed;
+
+#[fixed(kind=1, force, enum_as_int)]
+struct Foo {
+    kind: Kind,
+}
+
+#[fixed(name=4, force, no_rewrite)]
+struct Bar {
+    name: String,
+}
+
+fn main() {}
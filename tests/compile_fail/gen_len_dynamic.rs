@@ -0,0 +1,9 @@
+use fixed_size::fixed;
+
+#[fixed(s=4, gen_len)]
+struct Foo {
+    s: String,
+    notes: String,
+}
+
+fn main() {}
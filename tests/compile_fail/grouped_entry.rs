@@ -0,0 +1,21 @@
+use fixed_size::fixed;
+
+#[fixed(fields(a=4, b="x"))]
+struct Foo {
+    a: String,
+    b: String,
+}
+
+#[fixed(fields(a=4, b=(4, min=x)))]
+struct Bar {
+    a: String,
+    b: String,
+}
+
+#[fixed(fields(a=4, *=8))]
+struct Baz {
+    a: String,
+    b: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(fields(s=4, gen_len))]
+struct Foo {
+    s: String,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use fixed_size::fixed;
+
+#[fixed("s"=4, t=(), u=([4], MyString), 4, v=[4])]
+struct Malformed {
+    s: String,
+    t: String,
+    u: String,
+    v: String,
+}
+
+#[fixed(typ=heapless::String::<u8>, typ_shape="<{}>", s=4)]
+struct ShapedGeneric {
+    s: String,
+}
+
+fn main() {}
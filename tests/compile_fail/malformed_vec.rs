@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(items=4)]
+struct Foo {
+    items: Vec,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use fixed_size::fixed;
+
+#[fixed(s=4.0, gen_nothing, t=4, unit=words)]
+struct Foo {
+    s: String,
+    t: String,
+}
+
+fn main() {}
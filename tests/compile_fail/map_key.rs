@@ -0,0 +1,14 @@
+use std::collections::HashMap;
+use fixed_size::fixed;
+
+#[fixed(labels_key=8)]
+struct NotAMap {
+    labels: Vec<String>,
+}
+
+#[fixed(labels_key=8)]
+struct NotAString {
+    labels: HashMap<u32, String>,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(code=(4, min=5))]
+struct Airport {
+    code: String,
+}
+
+fn main() {}
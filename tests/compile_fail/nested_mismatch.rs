@@ -0,0 +1,13 @@
+use fixed_size::fixed;
+
+#[fixed(s=4, suffix=Fixed)]
+struct Inner {
+    s: String,
+}
+
+#[fixed(inner: Inner -> InnerFixed)]
+struct Outer {
+    inner: Vec<Inner>,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use fixed_size::fixed;
+
+#[fixed(s=N, u=4, gen_newtypes)]
+struct Foo<const N: usize> {
+    s: String,
+    u: String,
+}
+
+#[fixed(*=N, gen_newtypes)]
+struct Wildcard<const N: usize> {
+    s: String,
+}
+
+fn main() {}
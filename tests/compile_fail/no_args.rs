@@ -0,0 +1,18 @@
+use fixed_size::fixed;
+
+#[fixed]
+struct Foo {
+    s: String,
+}
+
+#[fixed()]
+struct Bar {
+    s: String,
+}
+
+#[fixed(gen_len)]
+struct Baz {
+    n: u32,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(s=4, gen_try_from, no_rewrite)]
+struct Setters {
+    s: String,
+}
+
+fn main() {}
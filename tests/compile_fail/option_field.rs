@@ -0,0 +1,17 @@
+use fixed_size::fixed;
+
+const LEN: usize = 4;
+
+#[fixed(s=4, typ=LEN)]
+struct Ambiguous {
+    s: String,
+    typ: String,
+}
+
+#[fixed(s=4, typ=4)]
+struct Literal {
+    s: String,
+    typ: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(s=99999999999999999999)]
+struct Foo {
+    s: String,
+}
+
+fn main() {}
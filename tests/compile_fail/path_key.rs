@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(foo::my_string=4)]
+struct Foo {
+    my_string: String,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+mod outer {
+    pub mod inner {
+        use fixed_size::fixed;
+
+        #[fixed(c=4, d=4)]
+        #[derive(Default)]
+        pub struct Foo {
+            pub(super) c: String,
+            d: String,
+        }
+    }
+}
+
+fn main() {
+    let foo = outer::inner::Foo::default();
+    let _ = foo.c;
+    let _ = foo.d;
+}
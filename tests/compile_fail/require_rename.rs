@@ -0,0 +1,12 @@
+use fixed_size::fixed;
+
+#[fixed(name=8, code=4, require_rename)]
+#[derive(serde::Serialize)]
+struct Foo {
+    #[serde(rename = "NAME")]
+    name: String,
+    #[serde(default)]
+    code: String,
+}
+
+fn main() {}
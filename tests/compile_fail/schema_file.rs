@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(schema="no/such/widths.txt")]
+struct Missing {
+    s: String,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use fixed_size::fixed;
+
+#[fixed(serde:"wire"=4)]
+#[derive(serde::Serialize)]
+struct Missing {
+    #[serde(rename = "other")]
+    name: String,
+}
+
+#[fixed(name=4, serde:"wire"=4)]
+#[derive(serde::Serialize)]
+struct Twice {
+    #[serde(rename = "wire")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use fixed_size::fixed;
+
+#[fixed(nick=4, serde_with)]
+#[derive(serde::Serialize)]
+struct Foo {
+    nick: Option<String>,
+}
+
+fn main() {}
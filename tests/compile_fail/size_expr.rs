@@ -0,0 +1,10 @@
+use fixed_size::fixed;
+
+#[fixed(s=4-4, t=2<<1, u=f()+1)]
+struct Foo {
+    s: String,
+    t: String,
+    u: String,
+}
+
+fn main() {}
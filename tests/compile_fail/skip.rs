@@ -0,0 +1,14 @@
+use fixed_size::fixed;
+
+#[fixed(s=4, skip(s))]
+struct Both {
+    s: String,
+}
+
+#[fixed(s=4, skip(nots))]
+struct Missing {
+    s: String,
+    notes: String,
+}
+
+fn main() {}
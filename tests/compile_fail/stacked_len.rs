@@ -0,0 +1,17 @@
+use fixed_size::fixed;
+
+#[fixed(a=4, gen_len)]
+#[fixed(b=8)]
+struct OnTheFirst {
+    a: String,
+    b: String,
+}
+
+#[fixed(a=4)]
+#[fixed(b=8, gen_bytes)]
+struct Bytes {
+    a: String,
+    b: String,
+}
+
+fn main() {}
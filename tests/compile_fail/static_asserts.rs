@@ -0,0 +1,12 @@
+use fixed_size::fixed;
+
+const EMPTY: usize = 0;
+const LEN: usize = 4;
+
+#[fixed(s=LEN, t=EMPTY, gen_static_asserts)]
+struct Record {
+    s: String,
+    t: String,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use fixed_size::fixed;
+
+#[fixed(s=(2, 4))]
+struct NotAVec {
+    s: String,
+}
+
+#[fixed(tags=(2, 4, 8))]
+struct TwoInner {
+    tags: Vec<String>,
+}
+
+#[fixed(tags=(2, 4, min=5))]
+struct MinTooLarge {
+    tags: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(a=4, b=4, strict)]
+struct Foo {
+    a: String,
+    b: ArrayString<4>,
+}
+
+#[fixed(a=2)]
+#[fixed(a=4, strict)]
+struct Bar {
+    a: String,
+}
+
+fn main() {}
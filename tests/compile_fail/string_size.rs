@@ -0,0 +1,13 @@
+use fixed_size::fixed;
+
+#[fixed(s="4")]
+struct Foo {
+    s: String,
+}
+
+#[fixed(t=("4", MyString))]
+struct Bar {
+    t: String,
+}
+
+fn main() {}
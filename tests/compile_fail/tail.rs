@@ -0,0 +1,22 @@
+use fixed_size::fixed;
+
+#[fixed(a=4, tail(payload))]
+struct NotLast {
+    payload: Vec<u8>,
+    a: String,
+}
+
+#[fixed(a=4, payload=8, tail(payload))]
+struct Sized {
+    a: String,
+    payload: Vec<u8>,
+}
+
+#[fixed(a=4, tail(payload, rest))]
+struct Two {
+    a: String,
+    payload: Vec<u8>,
+    rest: Vec<u8>,
+}
+
+fn main() {}
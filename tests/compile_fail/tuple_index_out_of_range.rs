@@ -0,0 +1,6 @@
+use fixed_size::fixed;
+
+#[fixed(0=4, 2=8)]
+struct Foo(String, String);
+
+fn main() {}
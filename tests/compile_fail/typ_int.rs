@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(typ=4, s=4)]
+struct Foo {
+    s: String,
+}
+
+fn main() {}
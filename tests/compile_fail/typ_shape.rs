@@ -0,0 +1,18 @@
+use fixed_size::fixed;
+
+#[fixed(s=4, typ_shape="<[u8; {}]>")]
+struct NoTyp {
+    s: String,
+}
+
+#[fixed(s=4, typ=smallstr::SmallString, typ_shape="<[u8; 4]>")]
+struct NoSize {
+    s: String,
+}
+
+#[fixed(s=4, typ=smallstr::SmallString, typ_shape="[u8; {}]")]
+struct NotGeneric {
+    s: String,
+}
+
+fn main() {}
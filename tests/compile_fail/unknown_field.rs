@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(my_strng=4)]
+struct Foo {
+    my_string: String,
+}
+
+fn main() {}
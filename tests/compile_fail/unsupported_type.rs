@@ -0,0 +1,8 @@
+use fixed_size::fixed;
+
+#[fixed(count=4)]
+struct Foo {
+    count: u32,
+}
+
+fn main() {}
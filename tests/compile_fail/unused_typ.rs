@@ -0,0 +1,16 @@
+use fixed_size::fixed;
+
+#[fixed(typ=heapless::String)]
+struct Empty {
+    s: String,
+}
+
+struct Inner;
+struct InnerFixed;
+
+#[fixed(inner: Inner -> InnerFixed, typ=heapless::String)]
+struct NestedOnly {
+    inner: Inner,
+}
+
+fn main() {}
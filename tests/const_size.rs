@@ -0,0 +1,44 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+const CALLSIGN_LEN: usize = 8;
+
+mod consts {
+    pub const DIGEST_LEN: usize = 16;
+}
+
+#[fixed(callsign=CALLSIGN_LEN, digest=consts::DIGEST_LEN, gen_len, gen_static_asserts)]
+#[derive(Default)]
+struct Pilot {
+    callsign: String,
+    digest: Vec<u8>,
+    id: u32,
+}
+
+#[fixed(*=CALLSIGN_LEN, 1=(consts::DIGEST_LEN, ArrayString))]
+struct Pair(String, String);
+
+#[fixed(callsign=CALLSIGN_LEN, suffix=Fixed)]
+struct Plain {
+    callsign: String,
+}
+
+#[test]
+fn const_sizes() {
+    let pilot = Pilot::default();
+    assert_eq!(pilot.callsign.capacity(), CALLSIGN_LEN);
+    assert_eq!(pilot.digest.len(), consts::DIGEST_LEN);
+    assert_eq!(pilot.id, 0);
+    assert_eq!(Pilot::FIXED_LEN, CALLSIGN_LEN + consts::DIGEST_LEN + 4);
+
+    let pair = Pair(ArrayString::new(), ArrayString::new());
+    assert_eq!(pair.0.capacity(), CALLSIGN_LEN);
+    assert_eq!(pair.1.capacity(), consts::DIGEST_LEN);
+}
+
+#[test]
+fn const_size_conversion() {
+    let fixed = PlainFixed::try_from(Plain { callsign: "MAVERICK".to_string() }).unwrap();
+    assert_eq!(fixed.callsign.as_str(), "MAVERICK");
+    assert!(PlainFixed::try_from(Plain { callsign: "MAVERICK!".to_string() }).is_err());
+}
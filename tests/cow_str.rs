@@ -0,0 +1,33 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+use std::borrow::Cow;
+
+#[fixed(name=8)]
+#[derive(Debug, PartialEq)]
+struct Foo<'a> {
+    name: Cow<'a, str>,
+}
+
+#[fixed(name=4, c=1, as_char, suffix=Fixed)]
+#[derive(Debug, PartialEq)]
+struct Bar<'a, 'b> {
+    name: Cow<'a, str>,
+    c: Cow<'a, str>,
+    tag: &'b str,
+}
+
+#[test]
+fn cow_str_becomes_owned() {
+    let foo = Foo { name: ArrayString::from("abc").unwrap() };
+    assert_eq!(foo.name.capacity(), 8);
+}
+
+#[test]
+fn conversions_keep_the_original_lifetimes() {
+    let name = String::from("ab");
+    let bar = Bar { name: Cow::Borrowed(&name), c: Cow::Owned("x".to_string()), tag: "t" };
+    let fixed: BarFixed<'_> = BarFixed::try_from(bar).unwrap();
+    assert_eq!((fixed.name.as_str(), fixed.c, fixed.tag), ("ab", 'x', "t"));
+    let bar = Bar::from(fixed);
+    assert_eq!(bar, Bar { name: Cow::Borrowed("ab"), c: Cow::Borrowed("x"), tag: "t" });
+}
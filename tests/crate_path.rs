@@ -0,0 +1,41 @@
+use fixed_size::fixed;
+
+mod reexports {
+    pub use arrayvec::*;
+}
+
+#[fixed(s=4, points=2, crate=crate::reexports, gen_try_from, suffix=Fixed)]
+#[derive(Default)]
+struct Foo {
+    s: String,
+    points: Vec<u32>,
+}
+
+#[fixed(s=4, crate=reexports, serde_pad)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Padded {
+    s: String,
+}
+
+#[test]
+fn types_use_the_crate_path() {
+    let mut foo = FooFixed::default();
+    let _: &reexports::ArrayString<4> = &foo.s;
+    let _: &reexports::ArrayVec<u32, 2> = &foo.points;
+    foo.set_s("abcd").unwrap();
+    assert!(foo.set_s("abcde").is_err());
+}
+
+#[test]
+fn conversion_uses_the_crate_path() {
+    let fixed = FooFixed::try_from(Foo { s: "ab".to_string(), points: vec![1, 2] }).unwrap();
+    assert_eq!(fixed.s.as_str(), "ab");
+    assert_eq!(&fixed.points[..], &[1, 2]);
+}
+
+#[test]
+fn helper_types_use_the_crate_path() {
+    let padded = Padded { s: reexports::ArrayString::from("ab").unwrap().into() };
+    let json = serde_json::to_string(&padded).unwrap();
+    assert_eq!(serde_json::from_str::<Padded>(&json).unwrap(), padded);
+}
@@ -0,0 +1,43 @@
+use arrayvec::{ArrayString, ArrayVec};
+use fixed_size::fixed;
+
+#[fixed(s=4, nick=6, digest=2, points=3, gen_debug)]
+struct Foo {
+    s: String,
+    nick: Option<String>,
+    digest: Vec<u8>,
+    points: Vec<u16>,
+    n: u32,
+}
+
+#[fixed(0=4, serde_pad, gen_debug)]
+struct Tuple(String, i8);
+
+#[fixed(name=3, unit=chars, gen_debug)]
+struct Chars<T> {
+    name: String,
+    extra: T,
+}
+
+#[test]
+fn debug_shows_capacity() {
+    let foo = Foo {
+        s: ArrayString::from("ab").unwrap(),
+        nick: None,
+        digest: [1, 2],
+        points: ArrayVec::from_iter([7]),
+        n: 3,
+    };
+    let expected = r#"Foo { s: "ab" (cap=4), nick: None (cap=6), digest: [1, 2] (cap=2), points: [7] (cap=3), n: 3 }"#;
+    assert_eq!(format!("{:?}", foo), expected);
+    let foo = Foo { nick: Some(ArrayString::from("x").unwrap()), ..foo };
+    assert!(format!("{:?}", foo).contains(r#"nick: Some("x") (cap=6)"#));
+}
+
+#[test]
+fn debug_for_tuples_and_chars() {
+    let t = Tuple(ArrayString::from("abc").unwrap().into(), -1);
+    assert_eq!(format!("{:?}", t), r#"Tuple("abc" (cap=4), -1)"#);
+    let c = Chars { name: ArrayString::from("é").unwrap(), extra: 'x' };
+    assert_eq!(format!("{:?}", c), r#"Chars { name: "é" (cap=3), extra: 'x' }"#);
+}
@@ -0,0 +1,54 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(s=4, gen_display)]
+#[derive(serde::Deserialize)]
+struct Nul {
+    s: String,
+}
+
+#[fixed(digest=2, name=8, code=4, nick=4, flag=1, as_char, pad=' ', gen_display)]
+struct Record {
+    digest: Vec<u8>,
+    name: String,
+    n: u32,
+    code: String,
+    nick: Option<String>,
+    flag: String,
+}
+
+#[test]
+fn nul_padding_is_trimmed() {
+    // deserialized without `serde_pad`, so the padding is kept in the value
+    let nul: Nul = serde_json::from_str(r#"{"s":"ab\u0000\u0000"}"#).unwrap();
+    assert_eq!(nul.s.len(), 4);
+    assert_eq!(nul.to_string(), "ab");
+}
+
+#[test]
+fn fields_are_separated() {
+    let mut r = Record {
+        digest: [0; 2],
+        name: ArrayString::from("Ann").unwrap().into(),
+        n: 1,
+        code: ArrayString::from("EG  ").unwrap().into(),
+        nick: None,
+        flag: 'Y',
+    };
+    assert_eq!(r.to_string(), "Ann EG  Y");
+    r.nick = Some(ArrayString::from("An").unwrap().into());
+    assert_eq!(format!("{}", r), "Ann EG An Y");
+    assert_eq!((r.n, r.digest), (1, [0; 2]));
+}
+
+#[fixed(code=4, amount=(6, align=right), pad=' ', gen_display)]
+struct Aligned {
+    code: String,
+    amount: String,
+}
+
+#[test]
+fn only_the_padded_side_is_trimmed() {
+    let a = Aligned { code: ArrayString::from("  ab").unwrap().into(), amount: ArrayString::from("  42  ").unwrap().into() };
+    assert_eq!(a.to_string(), "  ab 42  ");
+}
@@ -0,0 +1,64 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Kind {
+    Small = 1,
+    Large = 2,
+}
+
+impl TryFrom<u8> for Kind {
+    type Error = ();
+    fn try_from(v: u8) -> Result<Self, ()> {
+        match v {
+            1 => Ok(Kind::Small),
+            2 => Ok(Kind::Large),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(u32)]
+enum Status {
+    Ok = 200,
+    NotFound = 404,
+}
+
+impl TryFrom<u32> for Status {
+    type Error = ();
+    fn try_from(v: u32) -> Result<Self, ()> {
+        match v {
+            200 => Ok(Status::Ok),
+            404 => Ok(Status::NotFound),
+            _ => Err(()),
+        }
+    }
+}
+
+#[fixed(name=4, kind=1, status=4, enum_as_int, gen_bytes)]
+#[derive(Debug, Default)]
+struct Parcel {
+    name: String,
+    kind: Kind,
+    status: Status,
+}
+
+#[test]
+fn stored_as_integers() {
+    let mut p = Parcel { name: ArrayString::from("box").unwrap(), ..Parcel::default() };
+    let _: (u8, u32) = (p.kind, p.status);
+    p.set_kind(Kind::Small);
+    p.set_status(Status::NotFound);
+    assert_eq!((p.kind, p.status), (1, 404));
+    assert_eq!((p.kind(), p.status()), (Ok(Kind::Small), Ok(Status::NotFound)));
+    assert_eq!(Parcel::FIXED_LEN, 9);
+    assert_eq!(&p.to_bytes()[4..], &[1, 0x94, 0x01, 0, 0]);
+}
+
+#[test]
+fn invalid_discriminant() {
+    let p = Parcel { kind: 3, ..Parcel::default() };
+    assert_eq!(p.kind(), Err(ParcelFixedError::Discriminant { field: "kind", value: 3 }));
+    assert_eq!(p.status().unwrap_err().to_string(), "field `status` holds 0, which isn't a discriminant of its enum");
+}
@@ -0,0 +1,16 @@
+use fixed_size::{fixed, fixed_ty};
+
+// cargo sets `CARGO_PKG_VERSION_MAJOR` while compiling the test, so it is always there to read
+#[fixed(s=env("CARGO_PKG_VERSION_MAJOR"), t=(env("CARGO_PKG_VERSION_MAJOR") * 8, min=1), gen_schema)]
+struct Foo {
+    s: String,
+    t: String,
+}
+
+#[test]
+fn sizes_are_read_from_the_environment() {
+    let major: usize = env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap();
+    assert_eq!(Foo::FIXED_FIELDS, &[("s", major), ("t", major * 8)]);
+    let s: fixed_ty!(env("CARGO_PKG_VERSION_MAJOR")) = Default::default();
+    assert_eq!(s.capacity(), major);
+}
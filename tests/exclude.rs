@@ -0,0 +1,27 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(a=4, b=8, gen_debug, gen_display, gen_accessors, exclude_debug(b), exclude_accessors(a))]
+struct Foo {
+    a: String,
+    b: String,
+}
+
+#[fixed(0=4, 1=4, gen_debug, gen_str_eq, exclude_debug(1), exclude_str_eq(0))]
+struct Tuple(String, String);
+
+#[test]
+fn excluded_from_debug_only() {
+    let foo = Foo { a: ArrayString::from("ab").unwrap(), b: ArrayString::from("secret").unwrap() };
+    assert_eq!(format!("{:?}", foo), r#"Foo { a: "ab" (cap=4) }"#);
+    assert_eq!(foo.b.as_str(), "secret");
+    assert_eq!(foo.b(), "secret");
+    assert_eq!(foo.to_string(), "ab secret");
+}
+
+#[test]
+fn excluded_tuple_fields() {
+    let t = Tuple(ArrayString::from("ab").unwrap(), ArrayString::from("cd").unwrap());
+    assert_eq!(format!("{:?}", t), r#"Tuple("ab" (cap=4))"#);
+    assert!(t.eq_1("cd"));
+}
@@ -0,0 +1,34 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed]
+#[derive(Debug, Default, serde::Serialize)]
+struct Annotated {
+    #[fixed_field(4)]
+    #[serde(rename = "S")]
+    s: String,
+    #[fixed_field(2, min=2)]
+    code: Option<String>,
+    #[fixed_field(3)]
+    digest: Vec<u8>,
+    notes: String,
+}
+
+#[fixed(suffix=Fixed)]
+struct Mixed(#[fixed_field(4)] String, String);
+
+#[test]
+fn sizes_from_field_attributes() {
+    let a = Annotated { s: ArrayString::from("abcd").unwrap(), code: None, digest: [0; 3], notes: "any".to_string() };
+    assert_eq!((a.s.capacity(), a.digest.len()), (4, 3));
+    assert_eq!(Annotated { code: Some(ArrayString::from("a").unwrap()), ..Annotated::default() }.validate(),
+        Err(AnnotatedFixedError::TooShort { field: "code", min: 2, len: 1 }));
+    assert_eq!(serde_json::to_string(&a).unwrap(), r#"{"S":"abcd","code":null,"digest":[0,0,0],"notes":"any"}"#);
+}
+
+#[test]
+fn tuple_fields_and_siblings() {
+    let fixed = MixedFixed::try_from(Mixed("abcd".to_string(), "any".to_string())).unwrap();
+    assert_eq!(fixed.0.capacity(), 4);
+    assert_eq!(fixed.1, "any");
+}
@@ -0,0 +1,36 @@
+use fixed_size::{fixed, fixed_ty};
+
+mod reexports {
+    pub use arrayvec::*;
+}
+
+#[fixed(s=4, t=8, typ=heapless::String)]
+struct Foo {
+    s: String,
+    t: String,
+}
+
+#[fixed(s=4, crate=reexports)]
+struct Bar {
+    s: String,
+}
+
+struct ByHand {
+    s: fixed_ty!(4, crate=reexports),
+    t: fixed_ty!(8, typ=heapless::String),
+}
+
+#[test]
+fn same_types_as_the_attribute() {
+    let hand = ByHand { s: reexports::ArrayString::new(), t: heapless::String::new() };
+    let bar = Bar { s: hand.s };
+    let foo = Foo { s: heapless::String::new(), t: hand.t };
+    assert_eq!((bar.s.capacity(), foo.s.capacity(), foo.t.capacity()), (4, 4, 8));
+}
+
+#[test]
+fn default_type() {
+    use arrayvec::ArrayString;
+    let s: fixed_ty!(0x10) = ArrayString::new();
+    assert_eq!(s.capacity(), 16);
+}
@@ -0,0 +1,17 @@
+use fixed_size::fixed;
+
+#[test]
+fn struct_in_a_function_body() {
+    #[fixed(name=4, points=2, suffix=Fixed, gen_newtypes, serde_pad)]
+    #[derive(serde::Serialize)]
+    struct Local {
+        name: String,
+        points: Vec<u16>,
+    }
+
+    let local = LocalFixed::try_from(Local { name: "ab".to_string(), points: vec![1] }).unwrap();
+    assert_eq!((local.name.as_str(), &local.points[..]), ("ab", &[1][..]));
+    assert_eq!(serde_json::to_string(&local).unwrap(), r#"{"name":"ab\u0000\u0000","points":[1]}"#);
+    let err = LocalFixed::try_from(Local { name: "abcde".to_string(), points: vec![] }).err();
+    assert_eq!(err, Some(LocalFixedError::Overflow { field: "name", cap: 4, got: 5 }));
+}
@@ -0,0 +1,26 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+mod wire {
+    pub type Str = String;
+}
+
+type Name = String;
+
+#[fixed(name=8, code=2, nick=4, force, suffix=Fixed)]
+#[derive(Debug, PartialEq)]
+struct Foo {
+    name: wire::Str,
+    code: Name,
+    nick: Option<String>,
+    n: u32,
+}
+
+#[test]
+fn force_rewrites_aliases() {
+    let foo = Foo { name: "abc".to_string(), code: "xy".to_string(), nick: None, n: 1 };
+    let fixed = FooFixed::try_from(foo).unwrap();
+    let name: ArrayString<8> = fixed.name;
+    assert_eq!((name.as_str(), fixed.code.capacity()), ("abc", 2));
+    assert_eq!(Foo::from(fixed).code, "xy");
+}
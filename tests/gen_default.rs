@@ -0,0 +1,35 @@
+use fixed_size::fixed;
+
+#[derive(Default, Debug, PartialEq)]
+struct Inner(u8);
+
+#[fixed(name=4, nick=4, digest=40, points=3, flag=1, as_char, gen_default)]
+struct Record {
+    name: String,
+    nick: Option<String>,
+    digest: Vec<u8>,
+    points: Vec<u32>,
+    flag: String,
+    inner: Inner,
+    n: u64,
+}
+
+#[fixed(0=64, gen_default)]
+struct Pair<T>(Vec<u8>, T);
+
+#[test]
+fn empty_and_zeroed() {
+    let r = Record::default();
+    assert_eq!(r.name.as_str(), "");
+    assert!(r.nick.is_none());
+    assert_eq!(r.digest, [0; 40]);
+    assert!(r.points.is_empty());
+    assert_eq!(r.flag, '\0');
+    assert_eq!((r.inner, r.n), (Inner(0), 0));
+}
+
+#[test]
+fn generic_struct() {
+    let p = Pair::<u16>::default();
+    assert_eq!((p.0, p.1), ([0; 64], 0));
+}
@@ -0,0 +1,86 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(s=4, gen_try_from)]
+#[derive(Debug, Default)]
+struct WithLifetime<'a> {
+    s: String,
+    r: &'a str,
+}
+
+#[fixed(s=4, gen_try_from)]
+#[derive(Debug, Default)]
+struct WithType<T>
+where
+    T: Clone,
+{
+    s: String,
+    extra: T,
+}
+
+#[fixed(s=4)]
+#[derive(Debug)]
+struct WithConst<const N: usize> {
+    s: String,
+    extra: [u8; N],
+}
+
+#[fixed(s=4, gen_len)]
+struct ConstLen<const N: usize> {
+    s: String,
+    n: u32,
+}
+
+#[fixed(s=4, suffix=Fixed)]
+struct Sibling<'a, T: Clone, const N: usize> {
+    s: String,
+    r: &'a [T; N],
+}
+
+#[test]
+fn lifetime() {
+    let mut w = WithLifetime { s: ArrayString::new(), r: "borrowed" };
+    w.set_s("abcd").unwrap();
+    assert_eq!(w.s.capacity(), 4);
+    assert_eq!(w.r, "borrowed");
+}
+
+#[test]
+fn type_param() {
+    let mut w = WithType { s: ArrayString::new(), extra: vec![1u8] };
+    w.set_s("abcd").unwrap();
+    assert_eq!(w.s.capacity(), 4);
+    assert_eq!(w.extra, vec![1]);
+}
+
+#[test]
+fn const_param() {
+    let w = WithConst::<3> { s: ArrayString::new(), extra: [0; 3] };
+    assert_eq!(w.s.capacity(), 4);
+    assert_eq!(w.extra.len(), 3);
+    assert_eq!(ConstLen::<3>::FIXED_LEN, 8);
+}
+
+#[test]
+fn sibling_generics() {
+    let r = [1u8, 2];
+    let f = SiblingFixed::try_from(Sibling { s: "ab".to_string(), r: &r }).unwrap();
+    assert_eq!(f.s.as_str(), "ab");
+    assert_eq!(f.r, &[1, 2]);
+}
+
+#[fixed(payload=N, name=N, digest=4, gen_len, gen_static_asserts)]
+#[derive(Debug)]
+struct Frame<const N: usize> {
+    payload: Vec<u8>,
+    name: String,
+    digest: Vec<u8>,
+}
+
+#[test]
+fn size_from_const_param() {
+    let f = Frame::<8> { payload: [1; 8], name: ArrayString::from("abcdefgh").unwrap(), digest: [0; 4] };
+    assert_eq!((f.payload.len(), f.name.capacity()), (8, 8));
+    assert_eq!(Frame::<8>::FIXED_LEN, 20);
+    assert_eq!(Frame::<2>::FIXED_LEN, 8);
+}
@@ -0,0 +1,63 @@
+use arrayvec::{ArrayString, ArrayVec};
+use fixed_size::fixed;
+
+#[fixed(fields(s = 4, t = (8, min = 2), typ = 2, v = 3), gen_len)]
+struct Grouped {
+    s: String,
+    t: String,
+    typ: String,
+    v: Vec<u8>,
+}
+
+#[fixed(s=4, fields(t=8), typ=ArrayString)]
+struct Mixed {
+    s: String,
+    t: String,
+}
+
+#[fixed(street=8, suffix=Fixed)]
+struct Inner {
+    street: String,
+}
+
+#[fixed(fields(inner: Inner -> InnerFixed, points=2), suffix=Fixed)]
+struct Outer {
+    inner: Inner,
+    points: Vec<u32>,
+}
+
+#[fixed(fields(0=4, 1=2))]
+struct Pair(String, String);
+
+#[test]
+fn grouped_sizes() {
+    let g = Grouped {
+        s: ArrayString::new(),
+        t: ArrayString::from("ab").unwrap(),
+        typ: ArrayString::new(),
+        v: [0; 3],
+    };
+    assert_eq!((g.s.capacity(), g.t.capacity(), g.typ.capacity()), (4, 8, 2));
+    assert_eq!(Grouped::FIXED_LEN, 17);
+    assert!(g.validate().is_ok());
+}
+
+#[test]
+fn mixed_sizes() {
+    let m = Mixed { s: ArrayString::new(), t: ArrayString::new() };
+    assert_eq!((m.s.capacity(), m.t.capacity()), (4, 8));
+}
+
+#[test]
+fn grouped_nested() {
+    let outer = Outer { inner: Inner { street: "Main St".to_string() }, points: vec![1] };
+    let fixed = OuterFixed::try_from(outer).unwrap();
+    assert_eq!(fixed.inner.street.as_str(), "Main St");
+    assert_eq!(fixed.points, ArrayVec::<u32, 2>::from_iter([1]));
+}
+
+#[test]
+fn grouped_tuple() {
+    let p = Pair(ArrayString::new(), ArrayString::new());
+    assert_eq!((p.0.capacity(), p.1.capacity()), (4, 2));
+}
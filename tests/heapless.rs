@@ -0,0 +1,30 @@
+use fixed_size::fixed;
+
+#[fixed(typ=heapless::String, name=8)]
+#[derive(Debug, Default, PartialEq)]
+struct Foo {
+    name: String,
+}
+
+#[fixed(typ=heapless::Vec, readings=4)]
+#[derive(Debug, Default, PartialEq)]
+struct Bar {
+    readings: Vec<u16>,
+}
+
+#[test]
+fn heapless_string() {
+    let mut foo = Foo::default();
+    foo.name.push_str("abcdefgh").unwrap();
+    assert!(foo.name.push('i').is_err());
+    assert_eq!(foo.name.capacity(), 8);
+}
+
+#[test]
+fn heapless_vec() {
+    let mut bar = Bar::default();
+    for r in 0..4 {
+        bar.readings.push(r).unwrap();
+    }
+    assert!(bar.readings.push(4).is_err());
+}
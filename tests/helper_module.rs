@@ -0,0 +1,62 @@
+mod outer {
+    pub mod inner {
+        use fixed_size::fixed;
+
+        #[fixed(name=4, suffix=Fixed, serde_pad)]
+        pub struct User {
+            pub name: String,
+        }
+
+        #[fixed(name=4, suffix=Fixed, serde_pad)]
+        pub(super) struct Group {
+            pub name: String,
+        }
+
+        #[fixed(code=2, suffix=Fixed)]
+        pub(in crate::outer) struct Code {
+            pub code: String,
+        }
+
+        #[fixed(code=2, suffix=Fixed)]
+        struct Local {
+            code: String,
+        }
+
+        pub fn local(code: &str) -> Option<String> {
+            let local = LocalFixed::try_from(Local { code: code.to_string() });
+            local.map_err(|e| assert_eq!(e, LocalFixedError::Overflow { field: "code", cap: 2, got: code.len() }))
+                .ok().map(|l| l.code.to_string())
+        }
+    }
+
+    pub fn group(name: &str) -> Option<String> {
+        let group = inner::GroupFixed::try_from(inner::Group { name: name.to_string() });
+        group.map_err(|e| assert!(matches!(e, inner::GroupFixedError::Overflow { field: "name", .. })))
+            .ok().map(|g| g.name.to_string())
+    }
+
+    pub fn code(code: &str) -> Option<String> {
+        let fixed = inner::CodeFixed::try_from(inner::Code { code: code.to_string() });
+        fixed.map_err(|e| assert!(matches!(e, inner::CodeFixedError::Overflow { field: "code", .. })))
+            .ok().map(|c| c.code.to_string())
+    }
+}
+
+#[test]
+fn two_structs_in_one_module_get_their_own_helpers() {
+    use outer::inner::{User, UserFixed, UserFixedError};
+    let user = UserFixed::try_from(User { name: "bob".to_string() }).unwrap();
+    assert_eq!(user.name.as_str(), "bob");
+    let err = UserFixed::try_from(User { name: "alice".to_string() }).err();
+    assert_eq!(err, Some(UserFixedError::Overflow { field: "name", cap: 4, got: 5 }));
+    assert_eq!(outer::group("ann").as_deref(), Some("ann"));
+    assert_eq!(outer::group("alice"), None);
+}
+
+#[test]
+fn helpers_keep_the_struct_visibility() {
+    assert_eq!(outer::code("ab").as_deref(), Some("ab"));
+    assert_eq!(outer::code("abc"), None);
+    assert_eq!(outer::inner::local("ab").as_deref(), Some("ab"));
+    assert_eq!(outer::inner::local("abc"), None);
+}
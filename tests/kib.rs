@@ -0,0 +1,23 @@
+use arrayvec::ArrayVec;
+use fixed_size::fixed;
+
+const PAGES: usize = 2;
+
+#[fixed(buf=64, pages=PAGES, name=(1, min=1), vec_typ=ArrayVec, unit=kib, gen_schema, suffix=Fixed)]
+struct Foo {
+    buf: Vec<u8>,
+    pages: Vec<u8>,
+    name: String,
+}
+
+#[test]
+fn kib_sizes_are_scaled() {
+    let foo = FooFixed::try_from(Foo { buf: vec![1; 10], pages: vec![], name: "a".repeat(1024) }).unwrap();
+    let buf: &ArrayVec<u8, 65536> = &foo.buf;
+    let pages: &ArrayVec<u8, 2048> = &foo.pages;
+    assert_eq!((buf.len(), pages.capacity(), foo.name.capacity()), (10, 2048, 1024));
+    assert_eq!(FooFixed::FIXED_FIELDS, &[("buf", 65536), ("pages", 2048), ("name", 1024)]);
+    assert!(foo.validate().is_ok());
+    let short = FooFixed::try_from(Foo { buf: vec![], pages: vec![], name: "a".repeat(1023) }).unwrap();
+    assert_eq!(short.validate(), Err(FooFixedError::TooShort { field: "name", min: 1024, len: 1023 }));
+}
@@ -0,0 +1,37 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+use std::collections::{BTreeMap, HashMap};
+
+#[fixed(labels_key=(4, min=2), index_key=3, suffix=Fixed)]
+#[derive(Debug, PartialEq)]
+struct Record {
+    labels: HashMap<String, u32>,
+    index: BTreeMap<String, Vec<u8>>,
+}
+
+fn record(label: &str) -> Record {
+    Record {
+        labels: HashMap::from([(label.to_string(), 1)]),
+        index: BTreeMap::from([("a".to_string(), vec![1, 2])]),
+    }
+}
+
+#[test]
+fn keys_are_fixed() {
+    let fixed = RecordFixed::try_from(record("env")).unwrap();
+    assert_eq!(fixed.labels[&ArrayString::<4>::from("env").unwrap()], 1);
+    assert_eq!(fixed.index.keys().next().unwrap().capacity(), 3);
+    assert_eq!(Record::from(fixed), record("env"));
+}
+
+#[test]
+fn long_key_fails() {
+    let err = RecordFixed::try_from(record("region")).err();
+    assert_eq!(err, Some(RecordFixedError::Overflow { field: "labels", cap: 4, got: 6 }));
+}
+
+#[test]
+fn keys_are_validated() {
+    let fixed = RecordFixed::try_from(record("x")).unwrap();
+    assert_eq!(fixed.validate(), Err(RecordFixedError::TooShort { field: "labels", min: 2, len: 1 }));
+}
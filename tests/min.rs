@@ -0,0 +1,57 @@
+use arrayvec::{ArrayString, ArrayVec};
+use fixed_size::fixed;
+
+#[fixed(code=(4, min=4), name=(8, ArrayString, min=2), note=(8, min=1), tags=(3, min=1), other=8, suffix=Fixed)]
+#[derive(Default)]
+struct Record {
+    code: String,
+    name: String,
+    note: Option<String>,
+    tags: Vec<u32>,
+    other: String,
+}
+
+fn valid() -> RecordFixed {
+    RecordFixed {
+        code: ArrayString::from("EGLL").unwrap(),
+        name: ArrayString::from("ab").unwrap(),
+        note: None,
+        tags: ArrayVec::from_iter([1]),
+        other: ArrayString::new(),
+    }
+}
+
+#[test]
+fn valid_record() {
+    assert_eq!(valid().validate(), Ok(()));
+}
+
+#[test]
+fn too_short() {
+    let mut r = valid();
+    r.code = ArrayString::from("LH").unwrap();
+    let err = r.validate().unwrap_err();
+    assert_eq!(err, RecordFixedError::TooShort { field: "code", min: 4, len: 2 });
+    assert_eq!(err.to_string(), "field `code` has length 2, expected at least 4");
+}
+
+#[test]
+fn option_is_checked_when_some() {
+    let mut r = valid();
+    r.note = Some(ArrayString::new());
+    assert_eq!(r.validate(), Err(RecordFixedError::TooShort { field: "note", min: 1, len: 0 }));
+}
+
+#[test]
+fn vec_is_checked() {
+    let mut r = valid();
+    r.tags.clear();
+    assert_eq!(r.validate(), Err(RecordFixedError::TooShort { field: "tags", min: 1, len: 0 }));
+}
+
+#[test]
+fn error_is_std_error() {
+    fn takes(_: &dyn std::error::Error) {}
+    takes(&RecordFixedError::TooShort { field: "code", min: 4, len: 0 });
+    let _ = Record::default();
+}
@@ -0,0 +1,28 @@
+use fixed_size::fixed;
+
+#[fixed(code=4, name=(3, min=1), unit=chars, pad=' ', gen_mutators)]
+#[derive(Debug, Default)]
+struct Record {
+    code: String,
+    name: String,
+}
+
+#[test]
+fn appends_until_full() {
+    let mut r = Record::default();
+    r.code_try_push_str("AB").unwrap();
+    r.code_try_push_str("CD").unwrap();
+    assert_eq!(r.code.as_str(), "ABCD");
+    let err = r.code_try_push_str("E").unwrap_err();
+    assert_eq!(err.to_string(), "field `code` has length 5, expected at most 4");
+    assert_eq!(r.code.as_str(), "ABCD");
+}
+
+#[test]
+fn counts_chars() {
+    let mut r = Record::default();
+    r.name_try_push_str("é").unwrap();
+    r.name_try_push_str("éé").unwrap();
+    assert_eq!(r.name.as_str(), "ééé");
+    assert_eq!(r.name_try_push_str("a"), Err(RecordFixedError::Overflow { field: "name", cap: 3, got: 4 }));
+}
@@ -0,0 +1,54 @@
+use fixed_size::fixed;
+
+#[fixed(street=16, city=12, suffix=Fixed, gen_len)]
+#[derive(Debug)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+#[fixed(name=8, address: Address -> AddressFixed, suffix=Fixed, gen_len)]
+#[derive(Debug)]
+struct Person {
+    name: String,
+    address: Address,
+    age: u8,
+}
+
+#[fixed(0: Address -> AddressFixed, suffix=Fixed)]
+struct Wrapper(Address, u32);
+
+fn person(city: &str) -> Person {
+    Person {
+        name: "Ann".to_string(),
+        address: Address { street: "Main St".to_string(), city: city.to_string() },
+        age: 42,
+    }
+}
+
+#[test]
+fn nested_conversion() {
+    let fixed = PersonFixed::try_from(person("Springfield")).unwrap();
+    assert_eq!(fixed.name.as_str(), "Ann");
+    assert_eq!(fixed.address.street.as_str(), "Main St");
+    assert_eq!(fixed.address.city.as_str(), "Springfield");
+    assert_eq!(fixed.age, 42);
+}
+
+#[test]
+fn nested_overflow_is_an_error() {
+    assert!(PersonFixed::try_from(person("Llanfairpwllgwyngyll")).is_err());
+}
+
+#[test]
+fn nested_len() {
+    assert_eq!(PersonFixed::FIXED_LEN, 8 + 16 + 12 + 1);
+}
+
+#[test]
+fn nested_tuple_field() {
+    let address = Address { street: "Main St".to_string(), city: "Springfield".to_string() };
+    let fixed = WrapperFixed::try_from(Wrapper(address, 7)).unwrap();
+    assert_eq!(fixed.0.city.as_str(), "Springfield");
+    assert_eq!(fixed.1, 7);
+}
@@ -0,0 +1,61 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(product_code=8, warehouse_code=(8, min=2), note=4, gen_newtypes, gen_try_from, pad=' ')]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct Stock {
+    product_code: String,
+    warehouse_code: String,
+    note: Option<String>,
+    count: u32,
+}
+
+#[fixed(code=4, suffix=Fixed, gen_newtypes)]
+struct Order {
+    code: String,
+}
+
+#[test]
+fn distinct_types() {
+    assert_ne!(std::any::TypeId::of::<StockProductCode>(), std::any::TypeId::of::<StockWarehouseCode>());
+    assert_eq!(std::mem::size_of::<StockProductCode>(), std::mem::size_of::<StockPadded<8, b' ', false, false>>());
+}
+
+#[test]
+fn helpers_see_through_the_newtype() {
+    let mut stock = Stock::default();
+    stock.set_product_code("P-1").unwrap();
+    stock.set_warehouse_code("W").unwrap();
+    assert_eq!(stock.product_code.as_str(), "P-1");
+    assert_eq!(stock.validate(), Err(StockFixedError::TooShort { field: "warehouse_code", min: 2, len: 1 }));
+    assert!(stock.note.is_none());
+    assert_eq!(stock.count, 0);
+    let json = serde_json::to_string(&stock).unwrap();
+    assert_eq!(json, r#"{"product_code":"P-1     ","warehouse_code":"W       ","note":null,"count":0}"#);
+    assert_eq!(serde_json::from_str::<Stock>(&json).unwrap().product_code.as_str(), "P-1");
+}
+
+#[test]
+fn sibling_conversion() {
+    let fixed = OrderFixed::try_from(Order { code: "abcd".to_string() }).unwrap();
+    let _: &OrderFixedCode = &fixed.code;
+    assert_eq!(fixed.code.0, ArrayString::<4>::from("abcd").unwrap());
+}
+
+#[fixed(s=4, unit=chars, gen_newtypes, gen_try_from)]
+#[derive(Default)]
+struct Chars {
+    s: String,
+}
+
+#[test]
+fn newtypes_count_chars() {
+    assert_eq!(CharsS::try_from("éééé").unwrap().as_str(), "éééé");
+    let err = CharsS::try_from("ééééé").unwrap_err();
+    assert_eq!(err, CharsFixedError::Overflow { field: "s", cap: 4, got: 5 });
+    assert!(CharsS::try_from("abcdefg").is_err());
+    let mut chars = Chars::default();
+    assert!(chars.set_s("abcde").is_err());
+    chars.set_s("éé").unwrap();
+    assert_eq!(chars.s.as_str(), "éé");
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use fixed_size::fixed;
+
+#[fixed(name=4, alias=2, digest=3, tags=(2, 3), labels_key=2, gen_accessors, gen_default, no_rewrite)]
+#[derive(Debug)]
+struct Record {
+    name: String,
+    alias: Option<String>,
+    digest: Vec<u8>,
+    tags: Vec<String>,
+    labels: HashMap<String, u8>,
+}
+
+fn record() -> Record {
+    Record {
+        name: "abcd".to_string(),
+        alias: None,
+        digest: vec![1, 2, 3],
+        tags: vec!["abc".to_string()],
+        labels: HashMap::new(),
+    }
+}
+
+#[test]
+fn types_are_unchanged() {
+    let r: Record = record();
+    let _: &String = &r.name;
+    let _: &Vec<u8> = &r.digest;
+    assert_eq!(r.name(), "abcd");
+    assert_eq!(r.digest(), &[1, 2, 3]);
+    assert!(Record::default().validate().is_ok());
+}
+
+#[test]
+fn validate_rejects_over_length() {
+    assert!(record().validate().is_ok());
+    let over = |field, cap, got| Err(RecordFixedError::Overflow { field, cap, got });
+    assert_eq!(Record { name: "abcde".to_string(), ..record() }.validate(), over("name", 4, 5));
+    assert_eq!(Record { alias: Some("abc".to_string()), ..record() }.validate(), over("alias", 2, 3));
+    assert_eq!(Record { digest: vec![0; 4], ..record() }.validate(), over("digest", 3, 4));
+    assert_eq!(Record { tags: vec![String::new(); 3], ..record() }.validate(), over("tags", 2, 3));
+    assert_eq!(Record { tags: vec!["abcd".to_string()], ..record() }.validate(), over("tags", 3, 4));
+    let labels = HashMap::from([("abc".to_string(), 1)]);
+    assert_eq!(Record { labels, ..record() }.validate(), over("labels", 2, 3));
+}
+
+#[fixed(code=4, digest=2, gen_len, no_rewrite)]
+struct Frame {
+    code: String,
+    digest: Vec<u8>,
+    n: u16,
+}
+
+#[test]
+fn fixed_len_uses_the_sizes() {
+    assert_eq!(Frame::FIXED_LEN, 8);
+    let f = Frame { code: String::new(), digest: vec![1, 2, 3], n: 0 };
+    assert_eq!(f.n, 0);
+    assert_eq!(f.validate(), Err(FrameFixedError::Overflow { field: "digest", cap: 2, got: 3 }));
+}
@@ -0,0 +1,45 @@
+use fixed_size::fixed;
+
+#[fixed(amount=10, count=(3, num_fmt=none), name=4, num_fmt=zero_pad)]
+#[derive(Default)]
+struct Entry {
+    amount: String,
+    count: String,
+    name: String,
+}
+
+#[fixed(0=(6, num_fmt=zero_pad))]
+#[derive(Default)]
+struct Tuple(String);
+
+#[test]
+fn numbers_are_zero_padded_to_the_size() {
+    let mut entry = Entry::default();
+    entry.set_amount_num(42).unwrap();
+    assert_eq!(entry.amount.as_str(), "0000000042");
+    entry.set_amount_num(-42).unwrap();
+    assert_eq!(entry.amount.as_str(), "-000000042");
+    entry.set_amount_num(1.5).unwrap();
+    assert_eq!(entry.amount.as_str(), "00000001.5");
+    entry.set_amount_num("42").unwrap();
+    assert_eq!(entry.amount.as_str(), "0000000042");
+    entry.set_amount_num("+7").unwrap();
+    assert_eq!(entry.amount.as_str(), "+000000007");
+    entry.set_name_num(7u8).unwrap();
+    assert_eq!(entry.name.as_str(), "0007");
+    let mut tuple = Tuple::default();
+    tuple.set_0_num(123).unwrap();
+    assert_eq!(tuple.0.as_str(), "000123");
+}
+
+#[test]
+fn too_many_digits_is_an_error() {
+    let mut entry = Entry::default();
+    let err = entry.set_name_num(12345);
+    assert_eq!(err, Err(EntryFixedError::Overflow { field: "name", cap: 4, got: 5 }));
+    assert!(entry.name.is_empty());
+    let err = entry.set_amount_num("a very long value");
+    assert_eq!(err, Err(EntryFixedError::Overflow { field: "amount", cap: 10, got: 17 }));
+    let err = entry.set_name_num("éé€");
+    assert_eq!(err, Err(EntryFixedError::Overflow { field: "name", cap: 4, got: 7 }));
+}
@@ -0,0 +1,35 @@
+use arrayvec::ArrayVec;
+use fixed_size::fixed;
+
+#[fixed(sig=4, key=3, suffix=Fixed, gen_accessors)]
+#[derive(Debug, PartialEq)]
+struct Signed {
+    sig: Option<Vec<u8>>,
+    key: Option<Vec<u8>>,
+}
+
+#[fixed(sig=4, typ=ArrayVec)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Typed {
+    sig: Option<Vec<u8>>,
+}
+
+#[test]
+fn round_trip() {
+    let signed = Signed { sig: Some(b"abcd".to_vec()), key: None };
+    let fixed = SignedFixed::try_from(Signed { sig: Some(b"abcd".to_vec()), key: None }).unwrap();
+    assert_eq!((fixed.sig, fixed.key), (Some(*b"abcd"), None));
+    assert_eq!(fixed.sig(), Some(&b"abcd"[..]));
+    assert_eq!(Signed::from(fixed), signed);
+    let err = SignedFixed::try_from(Signed { sig: Some(b"abcde".to_vec()), key: None }).err();
+    assert_eq!(err, Some(SignedFixedError::Overflow { field: "sig", cap: 4, got: 5 }));
+}
+
+#[test]
+fn typ_applies_inside() {
+    for typed in [Typed { sig: None }, Typed { sig: Some(ArrayVec::from(*b"ab\0\0")) }] {
+        let encoded = bincode::serialize(&typed).unwrap();
+        let decoded: Typed = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(typed, decoded);
+    }
+}
@@ -0,0 +1,47 @@
+use arrayvec::{ArrayString, ArrayVec};
+use fixed_size::fixed;
+
+#[fixed(s=4, code=2, tag=1, points=2, suffix=Fixed)]
+struct Record {
+    s: String,
+    code: Option<String>,
+    tag: String,
+    points: Vec<u16>,
+}
+
+#[fixed(c=1, as_char, suffix=Fixed)]
+struct Flag {
+    c: String,
+}
+
+fn record() -> Record {
+    Record { s: "ab".to_string(), code: None, tag: "x".to_string(), points: vec![1] }
+}
+
+#[test]
+fn overflow_names_the_field() {
+    let r = Record { s: "abcde".to_string(), ..record() };
+    let err = RecordFixed::try_from(r).err().unwrap();
+    assert_eq!(err, RecordFixedError::Overflow { field: "s", cap: 4, got: 5 });
+    assert_eq!(err.to_string(), "field `s` has length 5, expected at most 4");
+}
+
+#[test]
+fn overflow_in_other_kinds() {
+    let r = Record { code: Some("abc".to_string()), ..record() };
+    assert_eq!(RecordFixed::try_from(r).err(), Some(RecordFixedError::Overflow { field: "code", cap: 2, got: 3 }));
+    let r = Record { points: vec![1, 2, 3], ..record() };
+    assert_eq!(RecordFixed::try_from(r).err(), Some(RecordFixedError::Overflow { field: "points", cap: 2, got: 3 }));
+    let r = Record { tag: "xy".to_string(), ..record() };
+    assert_eq!(RecordFixed::try_from(r).err(), Some(RecordFixedError::Overflow { field: "tag", cap: 1, got: 2 }));
+    let flag = |s: &str| FlagFixed::try_from(Flag { c: s.to_string() }).err();
+    assert_eq!(flag("ab"), Some(FlagFixedError::Overflow { field: "c", cap: 1, got: 2 }));
+    assert_eq!(flag(""), Some(FlagFixedError::TooShort { field: "c", min: 1, len: 0 }));
+}
+
+#[test]
+fn converts_when_it_fits() {
+    let fixed = RecordFixed::try_from(record()).unwrap();
+    assert_eq!(fixed.s, ArrayString::<4>::from("ab").unwrap());
+    assert_eq!(fixed.points, ArrayVec::<u16, 2>::from_iter([1]));
+}
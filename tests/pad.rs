@@ -0,0 +1,25 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(s=4, nick=6, code=(2, ArrayString), pad=0x2a, gen_try_from)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug, Default)]
+struct Foo {
+    s: String,
+    nick: Option<String>,
+    code: String,
+}
+
+#[test]
+fn pads_and_trims() {
+    let mut foo = Foo::default();
+    foo.set_s("ab").unwrap();
+    foo.nick = Some(ArrayString::from("xyz").unwrap().into());
+    let json = serde_json::to_string(&foo).unwrap();
+    assert_eq!(json, r#"{"s":"ab**","nick":"xyz***","code":""}"#);
+    assert_eq!(serde_json::from_str::<Foo>(&json).unwrap(), foo);
+}
+
+#[test]
+fn too_long_is_an_error() {
+    assert!(serde_json::from_str::<Foo>(r#"{"s":"abcde","nick":null,"code":""}"#).is_err());
+}
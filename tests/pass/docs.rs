@@ -0,0 +1,30 @@
+//! Docs on the struct and its fields must survive `#[fixed]`, or `missing_docs` fails the build.
+#![deny(missing_docs)]
+
+use fixed_size::fixed;
+
+/// A record
+///
+/// with a second paragraph.
+#[fixed(name=4)]
+#[derive(Debug)]
+#[doc = "and an attribute after the derive."]
+pub struct Record {
+    /// Replaced with a fixed string
+    #[allow(dead_code)]
+    /// and documented either side of another attribute.
+    pub name: String,
+    /// Left alone
+    pub count: u32,
+}
+
+/// A tuple struct
+#[fixed(0=8)]
+pub struct Pair(
+    /// Replaced in a tuple struct
+    pub String,
+    /// Left alone too
+    pub u8,
+);
+
+fn main() {}
@@ -0,0 +1,45 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[derive(Debug, Default, PartialEq)]
+struct CallSign<const CAP: usize>(ArrayString<CAP>);
+
+#[derive(Debug, Default, PartialEq)]
+struct Code<const CAP: usize>(ArrayString<CAP>);
+
+#[fixed(name=(8, CallSign), code=4)]
+#[derive(Debug, Default, PartialEq)]
+struct Station {
+    name: String,
+    code: String,
+}
+
+#[fixed(typ=Code, name=(8, CallSign), code=4, grid=6)]
+#[derive(Debug, Default, PartialEq)]
+struct Contact {
+    name: String,
+    code: String,
+    grid: String,
+}
+
+#[test]
+fn per_field_type_with_default() {
+    let station = Station {
+        name: CallSign(ArrayString::from("W1AW").unwrap()),
+        code: ArrayString::from("FN31").unwrap(),
+    };
+    assert_eq!(station.name.0.capacity(), 8);
+    assert_eq!(station.code.capacity(), 4);
+}
+
+#[test]
+fn per_field_type_overrides_typ() {
+    let contact = Contact {
+        name: CallSign(ArrayString::from("K1ABC").unwrap()),
+        code: Code(ArrayString::from("FN42").unwrap()),
+        grid: Code(ArrayString::from("FN42aa").unwrap()),
+    };
+    assert_eq!(contact.name.0.capacity(), 8);
+    assert_eq!(contact.code.0.capacity(), 4);
+    assert_eq!(contact.grid.0.capacity(), 6);
+}
@@ -0,0 +1,31 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(name=4)]
+#[derive(Clone, PartialEq, Debug, serde::Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+struct Record {
+    id: u32,
+    name: String,
+    tags: Vec<String>,
+    score: f32,
+    notes: String,
+}
+
+fn record() -> Record {
+    Record { id: 1, name: ArrayString::from("ab").unwrap(), tags: vec!["t".to_string()], score: 0.5, notes: "n".to_string() }
+}
+
+#[test]
+fn derives_are_kept() {
+    let r = record();
+    assert_eq!(r.clone(), r);
+}
+
+#[test]
+fn field_order_is_kept() {
+    let debug = r#"Record { id: 1, name: "ab", tags: ["t"], score: 0.5, notes: "n" }"#;
+    assert_eq!(format!("{:?}", record()), debug);
+    let json = r#"{"ID":1,"NAME":"ab","TAGS":["t"],"SCORE":0.5,"NOTES":"n"}"#;
+    assert_eq!(serde_json::to_string(&record()).unwrap(), json);
+}
@@ -0,0 +1,24 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(a=4, b=4, c=4, d=4, e=2)]
+struct Qualified {
+    a: ::std::string::String,
+    b: std::string::String,
+    c: ::std::option::Option<::std::string::String>,
+    d: ::std::boxed::Box<str>,
+    e: ::std::vec::Vec<u8>,
+}
+
+#[test]
+fn global_paths_are_rewritten() {
+    let q = Qualified {
+        a: ArrayString::<4>::new(),
+        b: ArrayString::<4>::new(),
+        c: None::<ArrayString<4>>,
+        d: ArrayString::<4>::new(),
+        e: [0u8; 2],
+    };
+    assert!(q.c.is_none());
+    assert_eq!((q.a.capacity(), q.b.capacity(), q.d.capacity(), q.e.len()), (4, 4, 4, 2));
+}
@@ -0,0 +1,37 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(r#type=4, name=8, r#ref=2, gen_try_from, gen_accessors, gen_str_eq, gen_len)]
+#[derive(Debug, Default)]
+struct Record {
+    r#type: String,
+    r#name: String,
+    r#ref: String,
+}
+
+#[fixed(r#type=4, skip(r#loop), suffix=Fixed)]
+struct Sibling {
+    r#type: String,
+    r#loop: String,
+}
+
+#[test]
+fn raw_and_plain_names_match() {
+    let mut r = Record::default();
+    r.set_type("abcd").unwrap();
+    r.set_name("frodo").unwrap();
+    r.set_ref("ab").unwrap();
+    assert_eq!((r.r#type.capacity(), r.r#name.capacity(), r.r#ref.capacity()), (4, 8, 2));
+    assert_eq!((r.r#type(), r.name(), r.r#ref()), ("abcd", "frodo", "ab"));
+    assert!(r.type_eq("abcd"));
+    assert_eq!(Record::FIXED_LEN, 14);
+    let err = r.set_type("abcde").unwrap_err();
+    assert_eq!(err.to_string(), "field `type` has length 5, expected at most 4");
+}
+
+#[test]
+fn raw_names_in_sibling_conversion() {
+    let fixed = SiblingFixed::try_from(Sibling { r#type: "ab".to_string(), r#loop: "any length".to_string() }).unwrap();
+    assert_eq!(fixed.r#type, ArrayString::<4>::from("ab").unwrap());
+    assert_eq!(fixed.r#loop, "any length");
+}
@@ -0,0 +1,49 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+use std::mem::{align_of, offset_of, size_of};
+
+#[fixed(s=4, repr=C)]
+struct C {
+    a: u8,
+    s: String,
+    b: u64,
+}
+
+#[fixed(s=4, repr=(packed, C))]
+struct Packed {
+    a: u8,
+    s: String,
+    b: u64,
+}
+
+#[fixed(s=4, repr=(C, packed), suffix=Fixed)]
+struct Both {
+    a: u8,
+    s: String,
+    b: u64,
+}
+
+#[test]
+fn repr_c_keeps_declaration_order() {
+    assert_eq!(offset_of!(C, a), 0);
+    assert!(offset_of!(C, s) < offset_of!(C, b));
+}
+
+#[test]
+fn repr_packed_has_no_padding() {
+    assert_eq!(align_of::<Packed>(), 1);
+    assert_eq!(size_of::<Packed>(), 1 + size_of::<ArrayString<4>>() + 8);
+}
+
+#[test]
+fn repr_only_applies_to_the_sibling() {
+    assert_eq!(align_of::<BothFixed>(), 1);
+    assert_eq!(offset_of!(BothFixed, a), 0);
+    assert!(align_of::<Both>() > 1);
+}
+
+#[test]
+fn packed_sibling_converts_back() {
+    let both = Both::from(BothFixed::try_from(Both { a: 1, s: "ab".to_string(), b: 2 }).unwrap());
+    assert_eq!((both.a, both.s.as_str(), both.b), (1, "ab", 2));
+}
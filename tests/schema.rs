@@ -0,0 +1,29 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+const LEN: usize = 12;
+
+#[fixed(*=2, z=LEN, b=4, a=8, gen_schema)]
+struct Record {
+    z: String,
+    n: u32,
+    b: Vec<u8>,
+    a: String,
+    w: Option<String>,
+}
+
+#[fixed(1=4, 0=2, gen_schema)]
+struct Pair(String, String);
+
+#[fixed(r#type=4, gen_schema)]
+struct Raw {
+    r#type: String,
+}
+
+#[test]
+fn declaration_order() {
+    assert_eq!(Record::FIXED_FIELDS, &[("z", LEN), ("b", 4), ("a", 8), ("w", 2)]);
+    assert_eq!(Pair::FIXED_FIELDS, &[("0", 2), ("1", 4)]);
+    assert_eq!(Raw::FIXED_FIELDS, &[("type", 4)]);
+    let _ = (Pair(ArrayString::new(), ArrayString::new()), Raw { r#type: ArrayString::new() });
+}
@@ -0,0 +1,30 @@
+use arrayvec::{ArrayString, ArrayVec};
+use fixed_size::fixed;
+
+#[fixed(schema="tests/widths.txt", suffix=Fixed)]
+struct Pilot {
+    callsign: String,
+    name: String,
+    digest: Vec<u16>,
+}
+
+#[fixed(schema="tests/widths.txt", callsign=4, skip(digest))]
+struct Overridden {
+    callsign: String,
+    name: String,
+    digest: Vec<u8>,
+}
+
+#[test]
+fn sizes_from_file() {
+    let fixed = PilotFixed::try_from(Pilot { callsign: "MAVERICK".to_string(), name: "P".to_string(), digest: vec![1] }).unwrap();
+    assert_eq!(fixed.callsign.capacity(), 8);
+    assert_eq!(fixed.digest, ArrayVec::<u16, 16>::from_iter([1]));
+    assert_eq!(fixed.validate(), Err(PilotFixedError::TooShort { field: "name", min: 2, len: 1 }));
+}
+
+#[test]
+fn inline_overrides_file() {
+    let o = Overridden { callsign: ArrayString::new(), name: ArrayString::new(), digest: vec![1, 2] };
+    assert_eq!((o.callsign.capacity(), o.name.capacity(), o.digest.len()), (4, 4, 2));
+}
@@ -0,0 +1,46 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+use serde::{Deserialize, Serialize};
+
+#[fixed(s=4, t=8)]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Foo {
+    #[serde(rename = "s2")]
+    s: String,
+    #[serde(default)]
+    #[serde(rename = "t2")]
+    t: String,
+}
+
+#[test]
+fn rename_survives() {
+    let foo = Foo { s: ArrayString::from("abcd").unwrap(), t: ArrayString::from("efgh").unwrap() };
+    let json = serde_json::to_string(&foo).unwrap();
+    assert_eq!(json, r#"{"s2":"abcd","t2":"efgh"}"#);
+    assert_eq!(serde_json::from_str::<Foo>(&json).unwrap(), foo);
+}
+
+#[test]
+fn default_survives() {
+    let foo: Foo = serde_json::from_str(r#"{"s2":"ab"}"#).unwrap();
+    assert_eq!(foo.s.as_str(), "ab");
+    assert!(foo.t.is_empty());
+}
+
+fn shout<S: serde::Serializer>(v: &ArrayString<4>, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&v.to_uppercase())
+}
+
+#[fixed(s=4)]
+#[derive(Serialize)]
+struct Bar {
+    #[serde(rename = "loud")]
+    #[serde(serialize_with = "shout")]
+    s: String,
+}
+
+#[test]
+fn serialize_with_survives() {
+    let bar = Bar { s: ArrayString::from("abcd").unwrap() };
+    assert_eq!(serde_json::to_string(&bar).unwrap(), r#"{"loud":"ABCD"}"#);
+}
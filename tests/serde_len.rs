@@ -0,0 +1,50 @@
+use fixed_size::fixed;
+
+#[fixed(payload=8, digest=4, serde_len, gen_len)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug, Default)]
+struct Packet {
+    payload: Vec<u8>,
+    digest: Vec<u8>,
+    seq: u16,
+}
+
+#[fixed(payload=4, serde_len, suffix=Fixed)]
+struct Message {
+    payload: Vec<u8>,
+}
+
+#[test]
+fn round_trip() {
+    let mut packet = Packet { seq: 7, ..Default::default() };
+    packet.payload.try_extend_from_slice(b"hello").unwrap();
+    packet.digest.push(0xff);
+    let encoded = bincode::serialize(&packet).unwrap();
+    assert_eq!(encoded.len(), Packet::FIXED_LEN);
+    assert_eq!(&encoded[..9], b"\x05\x00\x00\x00hello");
+    assert_eq!(&encoded[9..12], b"\x00\x00\x00");
+    let decoded: Packet = bincode::deserialize(&encoded).unwrap();
+    assert_eq!(packet, decoded);
+}
+
+#[test]
+fn json_round_trip() {
+    let mut packet = Packet::default();
+    packet.payload.push(1);
+    let json = serde_json::to_string(&packet).unwrap();
+    assert_eq!(json, r#"{"payload":[1,1,0,0,0,0,0,0,0],"digest":[0,0,0,0,0],"seq":0}"#);
+    assert_eq!(serde_json::from_str::<Packet>(&json).unwrap(), packet);
+}
+
+#[test]
+fn declared_length_too_long_is_an_error() {
+    let json = r#"{"payload":[9,1,0,0,0,0,0,0,0],"digest":[0,0,0,0,0],"seq":0}"#;
+    let err = serde_json::from_str::<Packet>(json).unwrap_err();
+    assert!(err.to_string().contains("invalid length 9"), "{}", err);
+}
+
+#[test]
+fn conversion() {
+    let fixed = MessageFixed::try_from(Message { payload: b"abcd".to_vec() }).unwrap();
+    assert_eq!(&fixed.payload[..], b"abcd");
+    assert!(MessageFixed::try_from(Message { payload: b"abcde".to_vec() }).is_err());
+}
@@ -0,0 +1,25 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(serde:"SeqNo"=8, fields(serde:"tag"=(4, min=1)), plain=2)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct Frame {
+    #[serde(rename(serialize = "SeqNo", deserialize = "SeqNo"))]
+    seq: String,
+    #[serde(default, rename = "tag")]
+    label: String,
+    plain: String,
+}
+
+#[test]
+fn sized_by_serde_name() {
+    let f = Frame {
+        seq: ArrayString::from("00000001").unwrap(),
+        label: ArrayString::from("ab").unwrap(),
+        plain: ArrayString::new(),
+    };
+    assert_eq!((f.seq.capacity(), f.label.capacity(), f.plain.capacity()), (8, 4, 2));
+    let json = serde_json::to_string(&f).unwrap();
+    assert_eq!(json, r#"{"SeqNo":"00000001","tag":"ab","plain":""}"#);
+    assert_eq!(serde_json::from_str::<Frame>(&json).unwrap(), f);
+}
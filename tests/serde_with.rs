@@ -0,0 +1,52 @@
+use fixed_size::fixed;
+
+#[fixed(code=4, name=(8, pad=' '), amount=(6, align=right, pad='0'), serde_with)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Record {
+    code: String,
+    name: String,
+    amount: String,
+}
+
+#[fixed(0=3, serde_with)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Tag(String, u8);
+
+fn record() -> Record {
+    Record { code: "ab".to_string(), name: "bob".to_string(), amount: "42".to_string() }
+}
+
+#[test]
+fn json_strings_are_exactly_their_size() {
+    let json = serde_json::to_string(&record()).unwrap();
+    assert_eq!(json, r#"{"code":"ab\u0000\u0000","name":"bob     ","amount":"000042"}"#);
+    assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record());
+}
+
+#[test]
+fn bincode_strings_are_exactly_their_size() {
+    let encoded = bincode::serialize(&record()).unwrap();
+    assert_eq!(encoded.len(), 3 * 8 + 4 + 8 + 6);
+    assert_eq!(&encoded[8..12], b"ab\0\0");
+    assert_eq!(&encoded[20..28], b"bob     ");
+    assert_eq!(&encoded[36..], b"000042");
+    assert_eq!(bincode::deserialize::<Record>(&encoded).unwrap(), record());
+}
+
+#[test]
+fn tuple_fields_are_padded() {
+    let tag = Tag("x".to_string(), 7);
+    let encoded = bincode::serialize(&tag).unwrap();
+    assert_eq!(&encoded[8..], b"x\0\0\x07");
+    assert_eq!(bincode::deserialize::<Tag>(&encoded).unwrap(), tag);
+}
+
+#[test]
+fn too_long_is_an_error_both_ways() {
+    let long = Record { code: "abcde".to_string(), ..record() };
+    let err = serde_json::to_string(&long).unwrap_err();
+    assert_eq!(err.to_string(), "field `code` holds 5, more than its size of 4");
+    assert_eq!(long.validate(), Err(RecordFixedError::Overflow { field: "code", cap: 4, got: 5 }));
+    let json = r#"{"code":"abcde","name":"bob","amount":"42"}"#;
+    assert!(serde_json::from_str::<Record>(json).is_err());
+}
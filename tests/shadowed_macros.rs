@@ -0,0 +1,50 @@
+// a `write!` of the user's own in scope mustn't be picked up by the generated code
+#[allow(unused_macros)]
+macro_rules! write {
+    ($($t:tt)*) => {
+        compile_error!("user write")
+    };
+}
+
+use fixed_size::fixed;
+
+#[fixed(s=4, code=(4, min=2), gen_try_from)]
+#[derive(Default)]
+struct Setters {
+    s: String,
+    code: String,
+}
+
+#[test]
+fn error_type_displays() {
+    let mut setters = Setters::default();
+    let err = setters.set_s("abcde").unwrap_err();
+    assert_eq!(err.to_string(), "field `s` has length 5, expected at most 4");
+    assert!(setters.validate().unwrap_err().to_string().contains("at least 2"));
+}
+
+#[fixed(s=4, serde_pad)]
+#[derive(serde::Deserialize)]
+struct Padded {
+    #[allow(dead_code)]
+    s: String,
+}
+
+#[test]
+fn padded_visitor_expects() {
+    let err = serde_json::from_str::<Padded>(r#"{"s":"abcde"}"#).err().unwrap();
+    assert!(err.to_string().contains("a string of at most 4 bytes"), "{}", err);
+}
+
+#[fixed(payload=4, serde_len)]
+#[derive(serde::Deserialize)]
+struct Prefixed {
+    #[allow(dead_code)]
+    payload: Vec<u8>,
+}
+
+#[test]
+fn prefixed_visitor_expects() {
+    let err = serde_json::from_str::<Prefixed>(r#"{"payload":[9,0,0,0,0]}"#).err().unwrap();
+    assert!(err.to_string().contains("a length of at most 4 followed by 4 bytes"), "{}", err);
+}
@@ -0,0 +1,71 @@
+use arrayvec::ArrayVec;
+use fixed_size::fixed;
+
+#[fixed(name=4, nick=4, bytes=4, items=2, suffix=Fixed)]
+#[derive(Debug, Clone, PartialEq)]
+struct Record {
+    name: String,
+    nick: Option<String>,
+    bytes: Vec<u8>,
+    items: Vec<u32>,
+    other: u64,
+}
+
+#[fixed(0=4, suffix=Fixed)]
+struct Pair(String, u8);
+
+fn record() -> Record {
+    Record {
+        name: "ab".to_string(),
+        nick: Some("cd".to_string()),
+        bytes: vec![1, 2],
+        items: vec![3],
+        other: 5,
+    }
+}
+
+#[test]
+fn original_is_untouched() {
+    let r = record();
+    let _: &String = &r.name;
+    let _: &Vec<u8> = &r.bytes;
+}
+
+#[test]
+fn converts_every_kind() {
+    let f = RecordFixed::try_from(record()).unwrap();
+    assert_eq!(f.name.as_str(), "ab");
+    assert_eq!(f.nick.unwrap().as_str(), "cd");
+    assert_eq!(f.bytes, [1, 2, 0, 0]);
+    assert_eq!(f.items, ArrayVec::<u32, 2>::from_iter([3]));
+    assert_eq!(f.other, 5);
+}
+
+#[test]
+fn overflow_is_an_error() {
+    let mut r = record();
+    r.bytes = vec![0; 5];
+    assert!(RecordFixed::try_from(r).is_err());
+    let mut r = record();
+    r.items = vec![1, 2, 3];
+    assert!(RecordFixed::try_from(r).is_err());
+    let mut r = record();
+    r.nick = Some("abcde".to_string());
+    assert!(RecordFixed::try_from(r).is_err());
+}
+
+#[test]
+fn tuple_struct() {
+    let p = PairFixed::try_from(Pair("abc".to_string(), 1)).unwrap();
+    assert_eq!(p.0.as_str(), "abc");
+    assert_eq!(p.1, 1);
+}
+
+#[test]
+fn converts_back() {
+    let r = Record::from(RecordFixed::try_from(record()).unwrap());
+    // byte arrays come back at their full length
+    assert_eq!(r, Record { bytes: vec![1, 2, 0, 0], ..record() });
+    let p = Pair::from(PairFixed::try_from(Pair("abc".to_string(), 1)).unwrap());
+    assert_eq!((p.0.as_str(), p.1), ("abc", 1));
+}
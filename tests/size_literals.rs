@@ -0,0 +1,41 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(s=4usize, t=8u8, gen_len)]
+struct Suffixed {
+    s: String,
+    t: String,
+}
+
+#[fixed(s=4, t=0x8, gen_len)]
+struct Plain {
+    s: String,
+    t: String,
+}
+
+#[test]
+fn suffix_is_stripped() {
+    let plain = Plain { s: ArrayString::from("abcd").unwrap(), t: ArrayString::new() };
+    // only compiles if the field types are identical
+    let suffixed = Suffixed { s: plain.s, t: plain.t };
+    assert_eq!(suffixed.s.capacity(), 4);
+    assert_eq!(Suffixed::FIXED_LEN, 12);
+    assert_eq!(Suffixed::FIXED_LEN, Plain::FIXED_LEN);
+}
+
+const BODY: usize = 8;
+
+#[fixed(s=4+2, t=(2 * (1 + 2), min=5), u=BODY - 2, digest=BODY / 2, gen_len)]
+struct Computed {
+    s: String,
+    t: String,
+    u: String,
+    digest: Vec<u8>,
+}
+
+#[test]
+fn arithmetic_sizes() {
+    let c = Computed { s: ArrayString::new(), t: ArrayString::new(), u: ArrayString::new(), digest: [0; 4] };
+    assert_eq!((c.s.capacity(), c.t.capacity(), c.u.capacity(), c.digest.len()), (6, 6, 6, 4));
+    assert_eq!(Computed::FIXED_LEN, 22);
+}
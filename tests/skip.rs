@@ -0,0 +1,22 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(*=8, skip(notes, other), code=4)]
+struct Record {
+    name: String,
+    code: String,
+    notes: String,
+    other: Option<String>,
+}
+
+#[fixed(*=4, skip(1))]
+struct Pair(String, String);
+
+#[test]
+fn skipped_fields_stay_variable() {
+    let r = Record { name: ArrayString::new(), code: ArrayString::new(), notes: String::from("free form"), other: None::<String> };
+    assert_eq!((r.name.capacity(), r.code.capacity(), r.notes.as_str()), (8, 4, "free form"));
+    assert!(r.other.is_none());
+    let p = Pair(ArrayString::new(), String::from("long text"));
+    assert_eq!((p.0.capacity(), p.1.as_str()), (4, "long text"));
+}
@@ -0,0 +1,92 @@
+use arrayvec::{ArrayString, ArrayVec};
+use fixed_size::fixed;
+
+#[fixed(a=4, digest=2)]
+#[fixed(b=8, points=3)]
+struct Stacked {
+    a: String,
+    b: String,
+    digest: Vec<u8>,
+    points: Vec<u32>,
+}
+
+#[fixed(a=4, nick=4, digest=4, points=4)]
+#[fixed(a=2, nick=2, digest=2, points=2)]
+struct Repeated {
+    a: String,
+    nick: Option<String>,
+    digest: Vec<u8>,
+    points: Vec<u32>,
+}
+
+#[fixed(a=4, typ=heapless::String)]
+#[fixed(a=2, typ=heapless::String)]
+struct Heapless {
+    a: String,
+}
+
+#[test]
+fn both_attributes_apply() {
+    let s = Stacked { a: ArrayString::new(), b: ArrayString::new(), digest: [0; 2], points: ArrayVec::new() };
+    assert_eq!((s.a.capacity(), s.b.capacity(), s.digest.len(), s.points.capacity()), (4, 8, 2, 3));
+}
+
+#[test]
+fn outer_attribute_wins() {
+    let r = Repeated { a: ArrayString::new(), nick: None::<ArrayString<4>>, digest: [0; 4], points: ArrayVec::new() };
+    assert_eq!((r.a.capacity(), r.digest.len(), r.points.capacity()), (4, 4, 4));
+    assert!(r.nick.is_none());
+}
+
+#[test]
+fn custom_type_is_left_alone() {
+    let h = Heapless { a: heapless::String::<4>::new() };
+    assert_eq!(h.a.capacity(), 4);
+}
+
+#[fixed(a=4, b=4)]
+struct HandWritten {
+    a: String,
+    b: ArrayString<8>,
+}
+
+#[test]
+fn lenient_by_default() {
+    let h = HandWritten { a: ArrayString::new(), b: ArrayString::new() };
+    assert_eq!((h.a.capacity(), h.b.capacity()), (4, 8));
+}
+
+#[fixed(a=4, gen_try_from, serde_pad)]
+#[fixed(b=8, gen_try_from, serde_pad)]
+#[derive(Default, serde::Serialize)]
+struct Generated {
+    a: String,
+    b: String,
+}
+
+#[test]
+fn helpers_are_generated_once() {
+    let mut g = Generated::default();
+    g.set_a("ab").unwrap();
+    g.set_b("abcdefgh").unwrap();
+    let e: GeneratedFixedError = g.set_a("abcde").unwrap_err();
+    assert!(e.to_string().contains("`a`"));
+    assert_eq!(serde_json::to_string(&g).unwrap(), r#"{"a":"ab\u0000\u0000","b":"abcdefgh"}"#);
+}
+
+#[fixed(a=4, gen_try_from)]
+#[fixed(b=8, digest=2, gen_try_from, gen_len)]
+#[derive(Default)]
+struct Measured {
+    a: String,
+    b: String,
+    digest: Vec<u8>,
+    n: u16,
+}
+
+#[test]
+fn last_attribute_sees_every_field() {
+    assert_eq!(Measured::FIXED_LEN, 4 + 8 + 2 + 2);
+    let mut m = Measured::default();
+    assert!(m.set_a("abcd").is_ok() && m.set_b("abcdefghi").is_err());
+}
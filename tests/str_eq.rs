@@ -0,0 +1,40 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(s=4, nick=6, flag=1, as_char, pad=' ', gen_str_eq)]
+struct Record {
+    s: String,
+    nick: Option<String>,
+    flag: String,
+}
+
+#[fixed(*=4, gen_str_eq)]
+struct Pair(String, String);
+
+#[fixed(s=4, amount=(4, align=right), pad=' ', gen_str_eq)]
+struct Aligned {
+    s: String,
+    amount: String,
+}
+
+#[test]
+fn compares_to_str() {
+    let r = Record { s: ArrayString::from("ab  ").unwrap().into(), nick: Some(ArrayString::from("jo").unwrap().into()), flag: 'y' };
+    assert!(r.s_eq("ab"));
+    assert!(!r.s_eq("abc"));
+    assert!(r.nick_eq(Some("jo")));
+    assert!(!r.nick_eq(None));
+    assert!(r.flag_eq("y"));
+    assert!(!r.flag_eq("yy"));
+    let p = Pair(ArrayString::from("a").unwrap(), ArrayString::from("b").unwrap());
+    assert!(p.eq_0("a") && p.eq_1("b"));
+}
+
+#[test]
+fn leading_pad_characters_are_data() {
+    let a = Aligned { s: ArrayString::from("  ab").unwrap().into(), amount: ArrayString::from(" 42 ").unwrap().into() };
+    assert!(a.s_eq("  ab"));
+    assert!(!a.s_eq("ab"));
+    assert!(a.amount_eq("42 "));
+    assert!(!a.amount_eq(" 42"));
+}
@@ -0,0 +1,32 @@
+use arrayvec::{ArrayString, ArrayVec};
+use fixed_size::fixed;
+
+#[fixed(tags=(2, 4, min=2), names=3, suffix=Fixed)]
+#[derive(Debug, PartialEq)]
+struct Record {
+    tags: Vec<String>,
+    names: Vec<String>,
+}
+
+fn record(tags: &[&str]) -> Record {
+    Record { tags: tags.iter().map(|t| t.to_string()).collect(), names: vec!["any length".to_string()] }
+}
+
+#[test]
+fn both_sizes_fixed() {
+    let fixed = RecordFixed::try_from(record(&["ab", "abcd"])).unwrap();
+    assert_eq!(fixed.tags.capacity(), 2);
+    assert_eq!(fixed.tags[1], ArrayString::<4>::from("abcd").unwrap());
+    let names: &ArrayVec<String, 3> = &fixed.names;
+    assert_eq!(names[0], "any length");
+    assert_eq!(Record::from(fixed), record(&["ab", "abcd"]));
+}
+
+#[test]
+fn overflow_and_min() {
+    let err = |tags: &[&str]| RecordFixed::try_from(record(tags)).err();
+    assert_eq!(err(&["a", "b", "c"]), Some(RecordFixedError::Overflow { field: "tags", cap: 2, got: 3 }));
+    assert_eq!(err(&["abcde"]), Some(RecordFixedError::Overflow { field: "tags", cap: 4, got: 5 }));
+    let fixed = RecordFixed::try_from(record(&["ab", "c"])).unwrap();
+    assert_eq!(fixed.validate(), Err(RecordFixedError::TooShort { field: "tags", min: 2, len: 1 }));
+}
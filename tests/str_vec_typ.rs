@@ -0,0 +1,31 @@
+use arrayvec::ArrayVec;
+use fixed_size::fixed;
+
+#[fixed(name=8, digest=4, points=2, str_typ=heapless::String, vec_typ=heapless::Vec)]
+struct Split {
+    name: String,
+    digest: Vec<u8>,
+    points: Vec<u16>,
+}
+
+#[fixed(name=8, points=2, typ=heapless::String, vec_typ=ArrayVec)]
+struct Override {
+    name: String,
+    points: Vec<u16>,
+}
+
+#[fixed(name=8, points=2, typ=heapless::Vec, str_typ=heapless::String)]
+struct Fallback {
+    name: String,
+    points: Vec<u16>,
+}
+
+#[test]
+fn separate_containers() {
+    let s = Split { name: heapless::String::new(), digest: heapless::Vec::new(), points: heapless::Vec::new() };
+    assert_eq!((s.name.capacity(), s.digest.capacity(), s.points.capacity()), (8, 4, 2));
+    let o = Override { name: heapless::String::new(), points: ArrayVec::new() };
+    assert_eq!((o.name.capacity(), o.points.capacity()), (8, 2));
+    let f = Fallback { name: heapless::String::new(), points: heapless::Vec::new() };
+    assert_eq!((f.name.capacity(), f.points.capacity()), (8, 2));
+}
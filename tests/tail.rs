@@ -0,0 +1,40 @@
+use fixed_size::fixed;
+
+#[fixed(magic=4, kind=2, tail(payload))]
+struct Frame {
+    magic: Vec<u8>,
+    kind: String,
+    len: u16,
+    payload: Vec<u8>,
+}
+
+#[fixed(tag=4, tail(body), gen_bytes, suffix=Fixed)]
+#[derive(Debug, PartialEq)]
+struct Message {
+    tag: String,
+    body: String,
+}
+
+#[fixed(0=2, tail(1), gen_len)]
+struct Tuple(String, Vec<u8>);
+
+#[test]
+fn fixed_len_excludes_the_tail() {
+    assert_eq!(Frame::FIXED_LEN, 8);
+    assert_eq!(Frame::header_len(), 8);
+    let frame = Frame { magic: *b"FRAM", kind: "ab".try_into().unwrap(), len: 3, payload: vec![1, 2, 3] };
+    assert_eq!(frame.total_len(), 11);
+    assert_eq!(Tuple::FIXED_LEN, 2);
+    assert_eq!(Tuple("ab".try_into().unwrap(), vec![0; 5]).total_len(), 7);
+}
+
+#[test]
+fn the_tail_keeps_its_type() {
+    let message = MessageFixed::try_from(Message { tag: "ping".to_string(), body: "hello".to_string() }).unwrap();
+    let body: &String = &message.body;
+    assert_eq!(body, "hello");
+    assert_eq!(message.to_bytes(), *b"ping");
+    let header = MessageFixed::from_bytes(b"pong").unwrap();
+    assert_eq!((header.tag.as_str(), header.body.as_str()), ("pong", ""));
+    assert_eq!(Message::from(message), Message { tag: "ping".to_string(), body: "hello".to_string() });
+}
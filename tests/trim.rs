@@ -0,0 +1,34 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+#[fixed(data=4, name=4, trim=none)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Verbatim {
+    data: String,
+    name: String,
+}
+
+#[fixed(data=(4, trim=none), name=4)]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct PerField {
+    data: String,
+    name: String,
+}
+
+#[test]
+fn trailing_nul_survives() {
+    let v = Verbatim { data: ArrayString::from("abc\0").unwrap().into(), name: ArrayString::from("ab").unwrap().into() };
+    let encoded = bincode::serialize(&v).unwrap();
+    let decoded: Verbatim = bincode::deserialize(&encoded).unwrap();
+    assert_eq!(decoded.data.as_str(), "abc\0");
+    assert_eq!(decoded.name.as_str(), "ab\0\0");
+}
+
+#[test]
+fn per_field_trim() {
+    let p = PerField { data: ArrayString::from("abc\0").unwrap().into(), name: ArrayString::from("ab").unwrap().into() };
+    let encoded = bincode::serialize(&p).unwrap();
+    let decoded: PerField = bincode::deserialize(&encoded).unwrap();
+    assert_eq!(decoded.data.as_str(), "abc\0");
+    assert_eq!(decoded.name.as_str(), "ab");
+}
@@ -0,0 +1,16 @@
+use fixed_size::fixed;
+
+#[fixed(s=4, gen_try_from, on_overflow=truncate)]
+#[derive(Default)]
+struct Foo {
+    s: String,
+}
+
+#[test]
+fn truncates_on_char_boundary() {
+    let mut foo = Foo::default();
+    foo.set_s("aéé");
+    assert_eq!(foo.s.as_str(), "aé");
+    foo.set_s("ab");
+    assert_eq!(foo.s.as_str(), "ab");
+}
@@ -0,0 +1,35 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+mod types {
+    pub mod strings {
+        #[derive(Debug, Default)]
+        pub struct MyString<const CAP: usize>(pub arrayvec::ArrayString<CAP>);
+    }
+}
+
+#[fixed(typ=crate::types::strings::MyString, s=4)]
+#[derive(Debug, Default)]
+struct Global {
+    s: String,
+}
+
+#[fixed(s=(4, types::strings::MyString), t=8)]
+#[derive(Debug, Default)]
+struct PerField {
+    s: String,
+    t: String,
+}
+
+#[test]
+fn qualified_typ() {
+    let g = Global { s: types::strings::MyString(ArrayString::from("abcd").unwrap()) };
+    assert_eq!(g.s.0.capacity(), 4);
+}
+
+#[test]
+fn qualified_per_field_typ() {
+    let p = PerField { s: types::strings::MyString(ArrayString::new()), t: ArrayString::new() };
+    assert_eq!(p.s.0.capacity(), 4);
+    assert_eq!(p.t.capacity(), 8);
+}
@@ -0,0 +1,44 @@
+use fixed_size::{fixed, fixed_ty};
+use smallstr::SmallString;
+use smallvec::SmallVec;
+
+const LEN: usize = 6;
+
+#[fixed(s=4, t=LEN, typ=heapless::String, typ_shape="<{}>")]
+struct ConstShape {
+    s: String,
+    t: String,
+}
+
+#[fixed(s=4, t=LEN, typ=SmallString, typ_shape="<[u8; {}]>", suffix=Fixed, gen_try_from)]
+struct ArrayShape {
+    s: String,
+    t: String,
+}
+
+#[fixed(points=3, typ=SmallVec, typ_shape="<[{T}; {}]>")]
+struct VecShape {
+    points: Vec<u16>,
+}
+
+#[test]
+fn const_generic_shape() {
+    let c = ConstShape { s: heapless::String::new(), t: heapless::String::new() };
+    assert_eq!((c.s.capacity(), c.t.capacity()), (4, LEN));
+}
+
+#[test]
+fn array_shape() {
+    let mut fixed = ArrayShapeFixed::try_from(ArrayShape { s: "abcd".to_string(), t: String::new() }).unwrap();
+    assert_eq!((fixed.s.inline_size(), fixed.t.inline_size()), (4, LEN));
+    assert_eq!(fixed.s.as_str(), "abcd");
+    assert_eq!(fixed.set_s("abcde"), Err(ArrayShapeFixedError::Overflow { field: "s", cap: 4, got: 5 }));
+    let v = VecShape { points: SmallVec::from_slice(&[1, 2]) };
+    assert_eq!(v.points.inline_size(), 3);
+}
+
+#[test]
+fn named_type() {
+    let s: fixed_ty!(4, typ=SmallString, typ_shape="<[u8; {}]>") = SmallString::new();
+    assert_eq!(s.inline_size(), 4);
+}
@@ -0,0 +1,99 @@
+use arrayvec::ArrayString;
+use fixed_size::fixed;
+
+const LEN: usize = 2;
+
+#[fixed(s=4, nick=(4, min=2), t=LEN, digest=4, unit=chars, suffix=Fixed, gen_try_from, gen_schema)]
+#[derive(Debug)]
+struct Record {
+    s: String,
+    nick: Option<String>,
+    t: String,
+    digest: Vec<u8>,
+}
+
+#[fixed(s=4, t=LEN, digest=4, unit=chars, gen_len)]
+struct Len {
+    s: String,
+    t: String,
+    digest: Vec<u8>,
+}
+
+#[fixed(s=4, unit=chars, gen_try_from, on_overflow=truncate)]
+#[derive(Default)]
+struct Truncated {
+    s: String,
+}
+
+#[fixed(s=4, unit=bytes, gen_try_from)]
+#[derive(Default)]
+struct Bytes {
+    s: String,
+}
+
+fn record(s: &str) -> Record {
+    Record { s: s.to_string(), nick: Some("éé".to_string()), t: "ab".to_string(), digest: vec![1] }
+}
+
+#[test]
+fn multi_byte_chars_fit() {
+    let fixed = RecordFixed::try_from(record("éééé")).unwrap();
+    assert!(fixed.validate().is_ok());
+    assert_eq!((fixed.s.as_str(), fixed.s.len()), ("éééé", 8));
+    assert_eq!((fixed.s.capacity(), fixed.t.capacity(), fixed.digest.len()), (16, 4 * LEN, 4));
+    assert_eq!(Len::FIXED_LEN, 16 + 4 * LEN + 4);
+    let _ = Len { s: fixed.s, t: fixed.t, digest: fixed.digest };
+    assert_eq!(RecordFixed::FIXED_FIELDS, &[("s", 4), ("nick", 4), ("t", LEN), ("digest", 4)]);
+}
+
+#[test]
+fn too_many_chars_is_an_error() {
+    assert!(RecordFixed::try_from(record("ééééé")).is_err());
+    assert!(RecordFixed::try_from(record("abcde")).is_err());
+    let mut r = record("ab");
+    r.nick = Some("ababa".to_string());
+    assert!(RecordFixed::try_from(r).is_err());
+}
+
+#[test]
+fn setters_count_chars() {
+    let mut fixed = RecordFixed::try_from(record("ab")).unwrap();
+    fixed.set_s("éééé").unwrap();
+    assert!(fixed.set_s("ééééé").is_err());
+    assert_eq!(fixed.s.as_str(), "éééé");
+
+    let mut t = Truncated::default();
+    t.set_s("éééééé");
+    assert_eq!(t.s.as_str(), "éééé");
+}
+
+#[test]
+fn min_counts_chars() {
+    let mut fixed = RecordFixed::try_from(record("ab")).unwrap();
+    fixed.nick = Some(ArrayString::from("é").unwrap());
+    assert_eq!(fixed.validate(), Err(RecordFixedError::TooShort { field: "nick", min: 2, len: 1 }));
+}
+
+#[test]
+fn bytes_is_the_default() {
+    let mut b = Bytes::default();
+    assert!(b.set_s("ééé").is_err());
+    b.set_s("éé").unwrap();
+    assert_eq!(b.s.capacity(), 4);
+}
+
+#[fixed(s=2, t=(3, align=right), unit=chars, serde_pad, pad=' ')]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Padded {
+    s: String,
+    t: String,
+}
+
+#[test]
+fn serde_pad_counts_chars() {
+    let p = Padded { s: ArrayString::from("a").unwrap().into(), t: ArrayString::from("é").unwrap().into() };
+    let json = serde_json::to_string(&p).unwrap();
+    assert_eq!(json, r#"{"s":"a ","t":"  é"}"#);
+    let back: Padded = serde_json::from_str(&json).unwrap();
+    assert_eq!((back.s.as_str(), back.t.as_str()), ("a", "é"));
+}
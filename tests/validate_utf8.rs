@@ -0,0 +1,25 @@
+use fixed_size::fixed;
+
+#[fixed(label=6, digest=2, validate_utf8, gen_bytes)]
+#[derive(Debug)]
+struct Record {
+    label: Vec<u8>,
+    digest: Vec<u8>,
+}
+
+#[test]
+fn text_passes() {
+    let r = Record { label: *b"h\xc3\xa9\0\0\0", digest: [0, 0] };
+    assert!(r.validate().is_ok());
+    assert!(Record::from_bytes(&r.to_bytes()).is_ok());
+}
+
+#[test]
+fn invalid_utf8_is_an_error() {
+    let r = Record { label: *b"ab\xff\0\0\0", digest: [0, 0] };
+    assert_eq!(r.validate(), Err(RecordFixedError::Utf8 { field: "label" }));
+    assert_eq!(Record::from_bytes(&r.to_bytes()).unwrap_err(), RecordFixedError::Utf8 { field: "label" });
+    // only checked up to the first pad byte
+    let r = Record { label: *b"ab\0\xff\0\0", digest: [0, 0] };
+    assert!(r.validate().is_ok());
+}
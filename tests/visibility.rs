@@ -0,0 +1,37 @@
+use arrayvec::ArrayString;
+
+mod outer {
+    pub mod inner {
+        use arrayvec::ArrayString;
+        use fixed_size::fixed;
+
+        #[fixed(a=4, b=4, c=4, d=4, gen_try_from)]
+        pub struct Foo {
+            pub a: String,
+            pub(crate) b: String,
+            pub(super) c: String,
+            d: String,
+        }
+
+        pub fn new() -> Foo {
+            Foo { a: ArrayString::new(), b: ArrayString::new(), c: ArrayString::new(), d: ArrayString::from("d").unwrap() }
+        }
+
+        pub fn d(v: &Foo) -> &str {
+            &v.d
+        }
+    }
+
+    pub fn c(v: &inner::Foo) -> &str {
+        &v.c
+    }
+}
+
+#[test]
+fn visibility_is_kept() {
+    let mut v = outer::inner::new();
+    v.a = ArrayString::from("a").unwrap();
+    v.b = ArrayString::from("b").unwrap();
+    v.set_c("c").unwrap();
+    assert_eq!((v.a.as_str(), v.b.as_str(), outer::c(&v), outer::inner::d(&v)), ("a", "b", "c", "d"));
+}
@@ -0,0 +1,60 @@
+use core::fmt::Write;
+use fixed_size::fixed;
+
+#[fixed(s=8, padded=4, serde_pad, gen_write)]
+#[derive(Default)]
+struct Padded {
+    s: String,
+    padded: String,
+}
+
+#[fixed(s=8, t=(4, heapless::String), gen_write, suffix=Fixed)]
+struct Typed {
+    s: String,
+    t: String,
+}
+
+#[fixed(0=6, gen_write, gen_newtypes)]
+#[derive(Default)]
+struct Tuple(String);
+
+#[test]
+fn writes_until_the_field_overflows() {
+    let mut foo = Padded::default();
+    write!(foo.s_writer(), "{}-{}", 12, 34).unwrap();
+    assert_eq!(foo.s.as_str(), "12-34");
+    write!(foo.s_writer(), "{}", 567).unwrap();
+    assert_eq!(foo.s.as_str(), "12-34567");
+    assert!(write!(foo.s_writer(), "8").is_err());
+    assert_eq!(foo.s.as_str(), "12-34567");
+    write!(foo.padded_writer(), "{:>4}", 1).unwrap();
+    assert_eq!(foo.padded.as_str(), "   1");
+}
+
+#[test]
+fn own_types_and_newtypes_are_written_to() {
+    let mut typed = TypedFixed::try_from(Typed { s: String::new(), t: "ab".to_string() }).unwrap();
+    write!(typed.t_writer(), "cd").unwrap();
+    assert!(write!(typed.t_writer(), "e").is_err());
+    assert_eq!(typed.t.as_str(), "abcd");
+    let mut tuple = Tuple::default();
+    write!(tuple.writer_0(), "{:06.2}", 1.5).unwrap();
+    assert_eq!(tuple.0.as_str(), "001.50");
+}
+
+#[fixed(s=4, unit=chars, gen_write)]
+#[derive(Default)]
+struct Chars {
+    s: String,
+}
+
+#[test]
+fn chars_sized_fields_count_chars() {
+    let mut c = Chars::default();
+    assert!(write!(c.s_writer(), "abcdefgh").is_err());
+    assert!(c.s.is_empty());
+    write!(c.s_writer(), "éé").unwrap();
+    write!(c.s_writer(), "éé").unwrap();
+    assert!(write!(c.s_writer(), "a").is_err());
+    assert_eq!(c.s.as_str(), "éééé");
+}